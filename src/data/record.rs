@@ -0,0 +1,188 @@
+use std::{fmt, str::FromStr};
+
+/// [`Record`] is one section of a data file in `assets/`, which starts with a
+/// `[kind id]` header and is followed by `key = value` fields. Blank lines and lines
+/// starting with `#` are ignored, and a key may be repeated to hold several values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    /// [`Record::kind`] is the first word of a [`Record`] header.
+    pub kind: String,
+    /// [`Record::id`] is the second word of a [`Record`] header.
+    pub id: String,
+    /// [`Record::line`] is the line number of a [`Record`] header.
+    pub line: usize,
+    fields: Vec<(String, String, usize)>,
+}
+
+impl Record {
+    /// [`Record::get`] returns [`Some`] value of the first field named `key`, and
+    /// [`None`] otherwise.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, v, _)| v.as_str())
+    }
+
+    /// [`Record::get_all`] returns the value and line number of every field named `key`.
+    pub fn get_all(&self, key: &str) -> Vec<(&str, usize)> {
+        self.fields
+            .iter()
+            .filter(|(k, _, _)| k == key)
+            .map(|(_, v, line)| (v.as_str(), *line))
+            .collect()
+    }
+
+    /// [`Record::get_line`] returns the line number of the field named `key`, or of
+    /// the [`Record`] itself if there isn't one.
+    pub fn get_line(&self, key: &str) -> usize {
+        self.fields
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map_or(self.line, |(_, _, line)| *line)
+    }
+
+    /// [`Record::require`] returns the value of the field named `key`, or a
+    /// [`DataError::MissingField`] if there isn't one.
+    pub fn require(&self, key: &str) -> Result<&str, DataError> {
+        self.get(key).ok_or_else(|| DataError::MissingField {
+            line: self.line,
+            id: self.id.clone(),
+            field: key.to_owned(),
+        })
+    }
+
+    /// [`Record::parse`] parses the value of the field named `key`, returning
+    /// `default` if there isn't one, or a [`DataError::InvalidField`] if it can't
+    /// be parsed.
+    pub fn parse<T: FromStr>(&self, key: &str, default: T) -> Result<T, DataError> {
+        Ok(self.parse_optional(key)?.unwrap_or(default))
+    }
+
+    /// [`Record::parse_optional`] parses the value of the field named `key`, returning
+    /// [`None`] if there isn't one, or a [`DataError::InvalidField`] if it can't
+    /// be parsed.
+    pub fn parse_optional<T: FromStr>(&self, key: &str) -> Result<Option<T>, DataError> {
+        self.fields
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, value, line)| parse_value(value, key, *line))
+            .transpose()
+    }
+
+    /// [`Record::parse_required`] parses the value of the field named `key`,
+    /// returning a [`DataError`] if it is missing or can't be parsed.
+    pub fn parse_required<T: FromStr>(&self, key: &str) -> Result<T, DataError> {
+        parse_value(self.require(key)?, key, self.get_line(key))
+    }
+}
+
+/// [`parse_value`] parses a single `value` of a field named `key` on `line`.
+pub fn parse_value<T: FromStr>(value: &str, key: &str, line: usize) -> Result<T, DataError> {
+    value.parse().map_err(|_| DataError::InvalidField {
+        line,
+        field: key.to_owned(),
+        value: value.to_owned(),
+    })
+}
+
+/// [`parse_records`] parses the `source` of a data file into a collection of [`Record`].
+pub fn parse_records(source: &str) -> Result<Vec<Record>, DataError> {
+    let mut records: Vec<Record> = Vec::new();
+    for (index, raw_line) in source.lines().enumerate() {
+        let line = index + 1;
+        let text = raw_line.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = text.strip_prefix('[') {
+            let Some(header) = header.strip_suffix(']') else {
+                return Err(DataError::Syntax {
+                    line,
+                    message: "a header must end with `]`".to_owned(),
+                });
+            };
+            let mut words = header.split_whitespace();
+            match (words.next(), words.next(), words.next()) {
+                (Some(kind), Some(id), None) => records.push(Record {
+                    kind: kind.to_owned(),
+                    id: id.to_owned(),
+                    line,
+                    fields: Vec::new(),
+                }),
+                _ => {
+                    return Err(DataError::Syntax {
+                        line,
+                        message: "a header must look like `[kind id]`".to_owned(),
+                    });
+                }
+            }
+        } else if let Some((key, value)) = text.split_once('=') {
+            let Some(record) = records.last_mut() else {
+                return Err(DataError::Syntax {
+                    line,
+                    message: "a field must come after a `[kind id]` header".to_owned(),
+                });
+            };
+            record
+                .fields
+                .push((key.trim().to_owned(), value.trim().to_owned(), line));
+        } else {
+            return Err(DataError::Syntax {
+                line,
+                message: format!("expected a `[kind id]` header or `key = value`, found `{text}`"),
+            });
+        }
+    }
+
+    Ok(records)
+}
+
+/// [`read_records`] reads the data file at `path` and parses it into a collection
+/// of [`Record`].
+pub fn read_records(path: &str) -> Result<Vec<Record>, DataError> {
+    let source = std::fs::read_to_string(path).map_err(|error| DataError::Io {
+        path: path.to_owned(),
+        error,
+    })?;
+    parse_records(&source)
+}
+
+/// [`DataError`] represents a problem reading or parsing a data file.
+#[derive(Debug)]
+pub enum DataError {
+    /// [`DataError::Io`] means the file at `path` couldn't be read.
+    Io { path: String, error: std::io::Error },
+    /// [`DataError::Syntax`] means a `line` isn't a header or a field.
+    Syntax { line: usize, message: String },
+    /// [`DataError::MissingField`] means a [`Record`] is missing a required `field`.
+    MissingField {
+        line: usize,
+        id: String,
+        field: String,
+    },
+    /// [`DataError::InvalidField`] means the `value` of a `field` couldn't be parsed.
+    InvalidField {
+        line: usize,
+        field: String,
+        value: String,
+    },
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::Io { path, error } => write!(f, "could not read `{path}`: {error}"),
+            DataError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            DataError::MissingField { line, id, field } => {
+                write!(f, "line {line}: `{id}` is missing the `{field}` field")
+            }
+            DataError::InvalidField { line, field, value } => {
+                write!(f, "line {line}: `{value}` is not a valid `{field}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
@@ -1,8 +1,24 @@
-use super::{event::Event, input::InputEvent};
+use super::{event::Event, input::InputEvent, mouse::MouseButton};
+use crate::{items::recipe::RecipeBook, ui};
 
 pub fn handle_crafting_input(event: InputEvent) -> Event {
     match event {
         InputEvent::Quit => Event::Back,
+        InputEvent::Up => Event::MoveCursor { x: 0, y: -1 },
+        InputEvent::Down => Event::MoveCursor { x: 0, y: 1 },
+        InputEvent::Interact => Event::Confirm,
+        InputEvent::MouseClick {
+            x,
+            y,
+            button: MouseButton::Left,
+        } => Event::LeftClicked { x, y },
         _ => Event::Nothing,
     }
 }
+
+pub fn handle_crafting_click(recipes: &RecipeBook, mouse_x: f32, mouse_y: f32) -> Event {
+    match ui::crafting::get_recipe_at(mouse_x, mouse_y, recipes.recipes().len()) {
+        Some(index) => Event::Select(index),
+        None => Event::Nothing,
+    }
+}
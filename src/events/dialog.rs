@@ -0,0 +1,24 @@
+use super::{event::Event, input::InputEvent, mouse::MouseButton};
+use crate::ui;
+
+pub fn handle_dialog_input(event: InputEvent) -> Event {
+    match event {
+        InputEvent::Quit => Event::Back,
+        InputEvent::Up => Event::MoveCursor { x: 0, y: -1 },
+        InputEvent::Down => Event::MoveCursor { x: 0, y: 1 },
+        InputEvent::Interact => Event::Confirm,
+        InputEvent::MouseClick {
+            x,
+            y,
+            button: MouseButton::Left,
+        } => Event::LeftClicked { x, y },
+        _ => Event::Nothing,
+    }
+}
+
+pub fn handle_dialog_click(text: &str, choice_count: usize, mouse_x: f32, mouse_y: f32) -> Event {
+    match ui::dialog::get_choice_at(mouse_x, mouse_y, text, choice_count) {
+        Some(index) => Event::Select(index),
+        None => Event::Nothing,
+    }
+}
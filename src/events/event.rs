@@ -1,15 +1,25 @@
+use crate::items::equipment::EquipSlot;
+
 /// [`Event`] rerpresents the result of an [`InputEvent`](super::input::InputEvent), and
 /// typically informs the [`GameState`](crate::game::state::GameState) what to do next.
 pub enum Event {
-    Continue,
-    Quit,
     MovePlayerForward,
     MovePlayerBackward,
     MovePlayerUp,
     MovePlayerDown,
+    MovePlayer { x: f32, y: f32 },
     LeftClicked { x: f32, y: f32 },
     RightClicked { x: f32, y: f32 },
+    Hovered { x: f32, y: f32 },
     Craft,
+    Interact,
+    Attack,
+    OpenInventory,
+    Pause,
+    MoveCursor { x: i32, y: i32 },
+    Select(usize),
+    Unequip(EquipSlot),
+    Confirm,
     Back,
     Nothing,
     Unknown,
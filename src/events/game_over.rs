@@ -0,0 +1,23 @@
+use super::{event::Event, input::InputEvent, mouse::MouseButton};
+use crate::ui;
+
+pub fn handle_game_over_input(event: InputEvent) -> Event {
+    match event {
+        InputEvent::Up => Event::MoveCursor { x: 0, y: -1 },
+        InputEvent::Down => Event::MoveCursor { x: 0, y: 1 },
+        InputEvent::Interact => Event::Confirm,
+        InputEvent::MouseClick {
+            x,
+            y,
+            button: MouseButton::Left,
+        } => Event::LeftClicked { x, y },
+        _ => Event::Nothing,
+    }
+}
+
+pub fn handle_game_over_click(mouse_x: f32, mouse_y: f32) -> Event {
+    match ui::game_over::get_option_at(mouse_x, mouse_y) {
+        Some(index) => Event::Select(index),
+        None => Event::Nothing,
+    }
+}
@@ -0,0 +1,246 @@
+use super::input::InputEvent;
+use std::fmt;
+
+/// [`MENU_TILT`] is how far a stick has to be tilted along an axis to move through a
+/// menu.
+pub const MENU_TILT: f32 = 0.5;
+
+/// [`GamepadButton`] is a button on a gamepad, named after the usual layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GamepadButton {
+    A,
+    B,
+    X,
+    Y,
+    Start,
+    Select,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    LeftBumper,
+    RightBumper,
+}
+
+impl GamepadButton {
+    /// [`GamepadButton::ALL`] is every [`GamepadButton`].
+    pub const ALL: [GamepadButton; 12] = [
+        GamepadButton::A,
+        GamepadButton::B,
+        GamepadButton::X,
+        GamepadButton::Y,
+        GamepadButton::Start,
+        GamepadButton::Select,
+        GamepadButton::DPadUp,
+        GamepadButton::DPadDown,
+        GamepadButton::DPadLeft,
+        GamepadButton::DPadRight,
+        GamepadButton::LeftBumper,
+        GamepadButton::RightBumper,
+    ];
+
+    /// [`GamepadButton::get_id`] gets a [`&str`](str) that identifies a
+    /// [`GamepadButton`] in the settings file.
+    pub fn get_id(&self) -> &str {
+        match self {
+            GamepadButton::A => "A",
+            GamepadButton::B => "B",
+            GamepadButton::X => "X",
+            GamepadButton::Y => "Y",
+            GamepadButton::Start => "Start",
+            GamepadButton::Select => "Select",
+            GamepadButton::DPadUp => "DPadUp",
+            GamepadButton::DPadDown => "DPadDown",
+            GamepadButton::DPadLeft => "DPadLeft",
+            GamepadButton::DPadRight => "DPadRight",
+            GamepadButton::LeftBumper => "LB",
+            GamepadButton::RightBumper => "RB",
+        }
+    }
+}
+
+impl std::str::FromStr for GamepadButton {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GamepadButton::ALL
+            .into_iter()
+            .find(|button| button.get_id() == s)
+            .ok_or(())
+    }
+}
+
+impl fmt::Display for GamepadButton {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_id())
+    }
+}
+
+/// [`Stick`] is one of the two analog sticks on a gamepad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stick {
+    Left,
+    Right,
+}
+
+impl std::str::FromStr for Stick {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Stick::Left),
+            "right" => Ok(Stick::Right),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Stick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stick::Left => write!(f, "left"),
+            Stick::Right => write!(f, "right"),
+        }
+    }
+}
+
+/// [`GamepadState`] will be implemented to tell an
+/// [`InputMap`](super::input_map::InputMap) which buttons are down and how far the
+/// sticks are tilted this frame.
+pub trait GamepadState {
+    /// [`GamepadState::is_button_down`] returns `true` while a [`GamepadButton`] is
+    /// held down.
+    fn is_button_down(&self, button: GamepadButton) -> bool;
+
+    /// [`GamepadState::is_button_pressed`] returns `true` on the frame a
+    /// [`GamepadButton`] is pressed.
+    fn is_button_pressed(&self, button: GamepadButton) -> bool;
+
+    /// [`GamepadState::get_stick`] returns how far a [`Stick`] is tilted on each axis,
+    /// from `-1.` to `1.`, where positive is right and down.
+    fn get_stick(&self, stick: Stick) -> (f32, f32);
+}
+
+/// [`GamepadSnapshot`] is the [`GamepadState`] of a gamepad during one frame. Backends
+/// fill one in from a real gamepad, and it can be made up to stand in for one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GamepadSnapshot {
+    /// [`GamepadSnapshot::down`] are the buttons held down.
+    pub down: Vec<GamepadButton>,
+    /// [`GamepadSnapshot::pressed`] are the buttons pressed this frame.
+    pub pressed: Vec<GamepadButton>,
+    /// [`GamepadSnapshot::left_stick`] is how far the left [`Stick`] is tilted.
+    pub left_stick: (f32, f32),
+    /// [`GamepadSnapshot::right_stick`] is how far the right [`Stick`] is tilted.
+    pub right_stick: (f32, f32),
+}
+
+impl GamepadState for GamepadSnapshot {
+    fn is_button_down(&self, button: GamepadButton) -> bool {
+        self.down.contains(&button)
+    }
+
+    fn is_button_pressed(&self, button: GamepadButton) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    fn get_stick(&self, stick: Stick) -> (f32, f32) {
+        match stick {
+            Stick::Left => self.left_stick,
+            Stick::Right => self.right_stick,
+        }
+    }
+}
+
+/// [`get_stick_direction`] returns the direction [`InputEvent`] of whichever axis an
+/// (`x`, `y`) stick tilt leans furthest along, once it passes [`MENU_TILT`], and
+/// [`InputEvent::Unknown`] otherwise.
+pub fn get_stick_direction(x: f32, y: f32) -> InputEvent {
+    if x.abs().max(y.abs()) < MENU_TILT {
+        InputEvent::Unknown
+    } else if x.abs() > y.abs() {
+        if x > 0. {
+            InputEvent::Right
+        } else {
+            InputEvent::Left
+        }
+    } else if y > 0. {
+        InputEvent::Down
+    } else {
+        InputEvent::Up
+    }
+}
+
+/// [`GilrsGamepad`] reads the first connected gamepad through `gilrs`.
+#[cfg(feature = "gamepad")]
+pub struct GilrsGamepad {
+    gilrs: gilrs::Gilrs,
+    last_down: Vec<GamepadButton>,
+}
+
+#[cfg(feature = "gamepad")]
+impl GilrsGamepad {
+    /// [`GilrsGamepad::new`] returns [`Some`] [`GilrsGamepad`], or [`None`] if
+    /// gamepads can't be read on this system.
+    pub fn new() -> Option<Self> {
+        let gilrs = gilrs::Gilrs::new().ok()?;
+        Some(Self {
+            gilrs,
+            last_down: Vec::new(),
+        })
+    }
+
+    /// [`GilrsGamepad::poll`] will be called every frame to return a
+    /// [`GamepadSnapshot`] of the first connected gamepad, which is empty if there
+    /// isn't one.
+    pub fn poll(&mut self) -> GamepadSnapshot {
+        // gilrs only updates a gamepad's state as its events are read
+        while self.gilrs.next_event().is_some() {}
+        let Some((_, gamepad)) = self.gilrs.gamepads().find(|(_, g)| g.is_connected()) else {
+            self.last_down.clear();
+            return GamepadSnapshot::default();
+        };
+        let down: Vec<GamepadButton> = GamepadButton::ALL
+            .into_iter()
+            .filter(|button| gamepad.is_pressed(map_button_to_gilrs(*button)))
+            .collect();
+        let pressed = down
+            .iter()
+            .filter(|button| !self.last_down.contains(button))
+            .copied()
+            .collect();
+        // gilrs points up for a positive y, the other way around from the screen
+        let snapshot = GamepadSnapshot {
+            down: down.clone(),
+            pressed,
+            left_stick: (
+                gamepad.value(gilrs::Axis::LeftStickX),
+                -gamepad.value(gilrs::Axis::LeftStickY),
+            ),
+            right_stick: (
+                gamepad.value(gilrs::Axis::RightStickX),
+                -gamepad.value(gilrs::Axis::RightStickY),
+            ),
+        };
+        self.last_down = down;
+        snapshot
+    }
+}
+
+#[cfg(feature = "gamepad")]
+fn map_button_to_gilrs(button: GamepadButton) -> gilrs::Button {
+    match button {
+        GamepadButton::A => gilrs::Button::South,
+        GamepadButton::B => gilrs::Button::East,
+        GamepadButton::X => gilrs::Button::West,
+        GamepadButton::Y => gilrs::Button::North,
+        GamepadButton::Start => gilrs::Button::Start,
+        GamepadButton::Select => gilrs::Button::Select,
+        GamepadButton::DPadUp => gilrs::Button::DPadUp,
+        GamepadButton::DPadDown => gilrs::Button::DPadDown,
+        GamepadButton::DPadLeft => gilrs::Button::DPadLeft,
+        GamepadButton::DPadRight => gilrs::Button::DPadRight,
+        GamepadButton::LeftBumper => gilrs::Button::LeftTrigger,
+        GamepadButton::RightBumper => gilrs::Button::RightTrigger,
+    }
+}
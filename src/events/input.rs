@@ -1,18 +1,36 @@
-use super::mouse::MouseButton;
+use super::{input_map::Binding, mouse::MouseButton};
 
 /// [`InputEvent`] represents player input to the game, and usually will result in an
 /// [`Event`](super::event::Event) to modify the [`GameState`](crate::game::state::GameState).
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum InputEvent {
     Up,
     Down,
     Left,
     Right,
     Interact,
-    Escape,
+    Attack,
+    Inventory,
     Quit,
     Character(char),
-    MouseClick { x: f32, y: f32, button: MouseButton },
-    MouseMove { x: f32, y: f32 },
+    /// [`InputEvent::KeyPressed`] is any key pressed, whether it is bound or not, i.e.:
+    /// to bind it to an [`Action`](super::input_map::Action).
+    KeyPressed(Binding),
+    MouseClick {
+        x: f32,
+        y: f32,
+        button: MouseButton,
+    },
+    MouseMove {
+        x: f32,
+        y: f32,
+    },
+    /// [`InputEvent::Move`] is an analog direction, i.e.: from a gamepad stick, where
+    /// each axis is from `-1.` to `1.` and a longer direction moves faster.
+    Move {
+        x: f32,
+        y: f32,
+    },
     // add more specific keys as needed:
     // tab, enter, space, delete, backspace, f keys (u8), ctrl, alt, shift...
     Unknown,
@@ -0,0 +1,753 @@
+use super::{
+    gamepad::{GamepadButton, GamepadState, Stick},
+    input::InputEvent,
+};
+use std::fmt;
+
+/// [`Key`] is a key on the keyboard, named the same way by every backend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    /// [`Key::Char`] is a letter or digit key, always stored in uppercase.
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Escape,
+    Space,
+    Tab,
+    Backspace,
+}
+
+impl Key {
+    /// [`Key::from_char`] returns [`Some`] [`Key`] for a letter or digit, and [`None`]
+    /// for any other character.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            ' ' => Some(Key::Space),
+            c if c.is_ascii_alphanumeric() => Some(Key::Char(c.to_ascii_uppercase())),
+            _ => None,
+        }
+    }
+
+    /// [`Key::all`] returns every [`Key`], letters and digits included.
+    pub fn all() -> impl Iterator<Item = Key> {
+        let named = [
+            Key::Up,
+            Key::Down,
+            Key::Left,
+            Key::Right,
+            Key::Enter,
+            Key::Escape,
+            Key::Space,
+            Key::Tab,
+            Key::Backspace,
+        ];
+        let chars = ('A'..='Z').chain('0'..='9').map(Key::Char);
+        named.into_iter().chain(chars)
+    }
+}
+
+impl std::str::FromStr for Key {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Up" => Ok(Key::Up),
+            "Down" => Ok(Key::Down),
+            "Left" => Ok(Key::Left),
+            "Right" => Ok(Key::Right),
+            "Enter" => Ok(Key::Enter),
+            "Escape" | "Esc" => Ok(Key::Escape),
+            "Space" => Ok(Key::Space),
+            "Tab" => Ok(Key::Tab),
+            "Backspace" => Ok(Key::Backspace),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c != ' ' => Key::from_char(c).ok_or(()),
+                    _ => Err(()),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Char(c) => write!(f, "{c}"),
+            Key::Up => write!(f, "Up"),
+            Key::Down => write!(f, "Down"),
+            Key::Left => write!(f, "Left"),
+            Key::Right => write!(f, "Right"),
+            Key::Enter => write!(f, "Enter"),
+            Key::Escape => write!(f, "Escape"),
+            Key::Space => write!(f, "Space"),
+            Key::Tab => write!(f, "Tab"),
+            Key::Backspace => write!(f, "Backspace"),
+        }
+    }
+}
+
+/// [`Modifiers`] are the modifier keys held down alongside a [`Key`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+/// [`Binding`] is a [`Key`] pressed while exactly some [`Modifiers`] are held, written
+/// as `Ctrl+Shift+S`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Binding {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+impl Binding {
+    /// [`Binding::new`] creates a new [`Binding`] for a `key` without modifiers.
+    pub fn new(key: Key) -> Self {
+        Self {
+            key,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    /// [`Binding::get_pressed`] returns [`Some`] [`Binding`] of a [`Key`] pressed this
+    /// frame on a [`KeyboardState`] with the [`Modifiers`] held, and [`None`] if no
+    /// [`Key`] was pressed.
+    pub fn get_pressed<K: KeyboardState>(keyboard: &K) -> Option<Self> {
+        Key::all()
+            .find(|key| keyboard.is_key_pressed(*key))
+            .map(|key| Binding {
+                key,
+                modifiers: keyboard.get_modifiers(),
+            })
+    }
+}
+
+impl std::str::FromStr for Binding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (modifier_names, key) = s.rsplit_once('+').unwrap_or(("", s));
+        let mut binding = Binding::new(key.trim().parse()?);
+        for modifier in modifier_names.split('+').filter(|m| !m.is_empty()) {
+            match modifier.trim() {
+                "Shift" => binding.modifiers.shift = true,
+                "Ctrl" => binding.modifiers.ctrl = true,
+                "Alt" => binding.modifiers.alt = true,
+                _ => return Err(()),
+            }
+        }
+        Ok(binding)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// [`Action`] is something the player can bind keys to, which becomes an
+/// [`InputEvent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Interact,
+    Attack,
+    Inventory,
+    Pause,
+}
+
+impl Action {
+    /// [`Action::ALL`] is every [`Action`] in the order they are shown.
+    pub const ALL: [Action; 8] = [
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::Interact,
+        Action::Attack,
+        Action::Inventory,
+        Action::Pause,
+    ];
+
+    /// [`Action::get_id`] gets a [`&str`](str) that identifies an [`Action`] in the
+    /// settings file.
+    pub fn get_id(&self) -> &str {
+        match self {
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::Interact => "interact",
+            Action::Attack => "attack",
+            Action::Inventory => "inventory",
+            Action::Pause => "pause",
+        }
+    }
+
+    /// [`Action::to_input`] returns the [`InputEvent`] an [`Action`] becomes.
+    pub fn to_input(&self) -> InputEvent {
+        match self {
+            Action::Up => InputEvent::Up,
+            Action::Down => InputEvent::Down,
+            Action::Left => InputEvent::Left,
+            Action::Right => InputEvent::Right,
+            Action::Interact => InputEvent::Interact,
+            Action::Attack => InputEvent::Attack,
+            Action::Inventory => InputEvent::Inventory,
+            Action::Pause => InputEvent::Quit,
+        }
+    }
+
+    /// [`Action::is_held`] returns `true` if an [`Action`] repeats every frame its key
+    /// is held down, rather than once when it is pressed.
+    pub fn is_held(&self) -> bool {
+        matches!(
+            self,
+            Action::Up | Action::Down | Action::Left | Action::Right
+        )
+    }
+}
+
+impl std::str::FromStr for Action {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Action::ALL
+            .into_iter()
+            .find(|action| action.get_id() == s)
+            .ok_or(())
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_id())
+    }
+}
+
+/// [`KeyboardState`] will be implemented by each backend to tell an [`InputMap`]
+/// which keys are down this frame.
+pub trait KeyboardState {
+    /// [`KeyboardState::is_key_down`] returns `true` while a [`Key`] is held down.
+    fn is_key_down(&self, key: Key) -> bool;
+
+    /// [`KeyboardState::is_key_pressed`] returns `true` on the frame a [`Key`] is
+    /// pressed.
+    fn is_key_pressed(&self, key: Key) -> bool;
+
+    /// [`KeyboardState::get_modifiers`] returns the [`Modifiers`] held down.
+    fn get_modifiers(&self) -> Modifiers;
+}
+
+// how far a stick can be tilted before it counts, by default
+const DEFAULT_DEAD_ZONE: f32 = 0.2;
+
+/// [`InputMap`] turns the keys of a [`KeyboardState`] and the buttons and sticks of a
+/// [`GamepadState`] into [`InputEvent`], through the [`Binding`] and [`GamepadButton`]
+/// of each [`Action`]. An [`Action`] can have several bindings, but a [`Binding`] or
+/// [`GamepadButton`] only ever belongs to one [`Action`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputMap {
+    bindings: Vec<(Action, Binding)>,
+    buttons: Vec<(Action, GamepadButton)>,
+    /// [`InputMap::stick`] is the [`Stick`] that moves the player.
+    pub stick: Stick,
+    /// [`InputMap::dead_zone`] is how far the [`InputMap::stick`] can be tilted before
+    /// it counts, from `0.` to `1.`, so a worn stick doesn't drift.
+    pub dead_zone: f32,
+}
+
+impl InputMap {
+    /// [`InputMap::empty`] creates a new [`InputMap`] without any bindings.
+    pub fn empty() -> Self {
+        Self {
+            bindings: Vec::new(),
+            buttons: Vec::new(),
+            stick: Stick::Left,
+            dead_zone: DEFAULT_DEAD_ZONE,
+        }
+    }
+
+    /// [`InputMap::bind`] binds a [`Binding`] to an [`Action`]. An
+    /// [`InputMapError::Conflict`] is returned if it is already bound to another
+    /// [`Action`].
+    pub fn bind(&mut self, action: Action, binding: Binding) -> Result<(), InputMapError> {
+        match self.get_action(binding) {
+            Some(bound) if bound == action => Ok(()),
+            Some(bound) => Err(InputMapError::Conflict {
+                binding,
+                action,
+                bound,
+            }),
+            None => {
+                self.bindings.push((action, binding));
+                Ok(())
+            }
+        }
+    }
+
+    /// [`InputMap::unbind`] removes a [`Binding`] from whichever [`Action`] it is
+    /// bound to.
+    pub fn unbind(&mut self, binding: Binding) {
+        self.bindings.retain(|(_, b)| *b != binding);
+    }
+
+    /// [`InputMap::bindings`] returns every [`Action`] and [`Binding`] pair, in the
+    /// order they were bound.
+    pub fn bindings(&self) -> &[(Action, Binding)] {
+        &self.bindings
+    }
+
+    /// [`InputMap::get_bindings`] returns every [`Binding`] of an [`Action`].
+    pub fn get_bindings(&self, action: Action) -> Vec<Binding> {
+        self.bindings
+            .iter()
+            .filter(|(a, _)| *a == action)
+            .map(|(_, binding)| *binding)
+            .collect()
+    }
+
+    /// [`InputMap::describe`] returns every [`Binding`] of an [`Action`] written out
+    /// for prompts, i.e.: `E/Enter`, or `Unbound` if it has none.
+    pub fn describe(&self, action: Action) -> String {
+        let bindings: Vec<String> = self
+            .get_bindings(action)
+            .iter()
+            .map(|binding| binding.to_string())
+            .collect();
+        if bindings.is_empty() {
+            "Unbound".to_owned()
+        } else {
+            bindings.join("/")
+        }
+    }
+
+    /// [`InputMap::get_action`] returns [`Some`] [`Action`] a [`Binding`] is bound to,
+    /// and [`None`] if it isn't bound.
+    pub fn get_action(&self, binding: Binding) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, b)| *b == binding)
+            .map(|(action, _)| *action)
+    }
+
+    /// [`InputMap::bind_button`] binds a [`GamepadButton`] to an [`Action`]. An
+    /// [`InputMapError::ButtonConflict`] is returned if it is already bound to another
+    /// [`Action`].
+    pub fn bind_button(
+        &mut self,
+        action: Action,
+        button: GamepadButton,
+    ) -> Result<(), InputMapError> {
+        match self.get_button_action(button) {
+            Some(bound) if bound == action => Ok(()),
+            Some(bound) => Err(InputMapError::ButtonConflict {
+                button,
+                action,
+                bound,
+            }),
+            None => {
+                self.buttons.push((action, button));
+                Ok(())
+            }
+        }
+    }
+
+    /// [`InputMap::unbind_button`] removes a [`GamepadButton`] from whichever
+    /// [`Action`] it is bound to.
+    pub fn unbind_button(&mut self, button: GamepadButton) {
+        self.buttons.retain(|(_, b)| *b != button);
+    }
+
+    /// [`InputMap::buttons`] returns every [`Action`] and [`GamepadButton`] pair, in
+    /// the order they were bound.
+    pub fn buttons(&self) -> &[(Action, GamepadButton)] {
+        &self.buttons
+    }
+
+    /// [`InputMap::get_buttons`] returns every [`GamepadButton`] of an [`Action`].
+    pub fn get_buttons(&self, action: Action) -> Vec<GamepadButton> {
+        self.buttons
+            .iter()
+            .filter(|(a, _)| *a == action)
+            .map(|(_, button)| *button)
+            .collect()
+    }
+
+    /// [`InputMap::get_button_action`] returns [`Some`] [`Action`] a
+    /// [`GamepadButton`] is bound to, and [`None`] if it isn't bound.
+    pub fn get_button_action(&self, button: GamepadButton) -> Option<Action> {
+        self.buttons
+            .iter()
+            .find(|(_, b)| *b == button)
+            .map(|(action, _)| *action)
+    }
+
+    /// [`InputMap::reset_bindings`] puts back the default [`Binding`] of every
+    /// [`Action`].
+    pub fn reset_bindings(&mut self) {
+        self.bindings = InputMap::default().bindings;
+    }
+
+    /// [`InputMap::reset_buttons`] puts back the default [`GamepadButton`] of every
+    /// [`Action`].
+    pub fn reset_buttons(&mut self) {
+        self.buttons = InputMap::default().buttons;
+    }
+
+    /// [`InputMap::map_input`] returns an [`InputEvent`] for every [`Action`] with a
+    /// [`Binding`] that is active on a [`KeyboardState`]. A [`Binding`] is only active
+    /// while exactly its [`Modifiers`] are held, except that one without any also
+    /// ignores shift, as long as its key isn't bound with shift as well.
+    pub fn map_input<K: KeyboardState>(&self, keyboard: &K) -> Vec<InputEvent> {
+        let modifiers = keyboard.get_modifiers();
+        let is_shift_only = modifiers
+            == Modifiers {
+                shift: true,
+                ..Default::default()
+            };
+        let is_matching = |binding: &Binding| {
+            // shift on its own, i.e.: from typing in capitals, still presses the plain key
+            binding.modifiers == modifiers
+                || (is_shift_only
+                    && binding.modifiers == Modifiers::default()
+                    && self
+                        .get_action(Binding {
+                            key: binding.key,
+                            modifiers,
+                        })
+                        .is_none())
+        };
+        Action::ALL
+            .iter()
+            .filter(|action| {
+                self.get_bindings(**action).iter().any(|binding| {
+                    is_matching(binding)
+                        && if action.is_held() {
+                            keyboard.is_key_down(binding.key)
+                        } else {
+                            keyboard.is_key_pressed(binding.key)
+                        }
+                })
+            })
+            .map(|action| action.to_input())
+            .collect()
+    }
+
+    /// [`InputMap::map_gamepad`] returns an [`InputEvent`] for every [`Action`] with a
+    /// [`GamepadButton`] that is active on a [`GamepadState`], followed by an
+    /// [`InputEvent::Move`] if the [`InputMap::stick`] is tilted past the
+    /// [`InputMap::dead_zone`].
+    pub fn map_gamepad<G: GamepadState>(&self, gamepad: &G) -> Vec<InputEvent> {
+        let mut events: Vec<InputEvent> = Action::ALL
+            .iter()
+            .filter(|action| {
+                self.get_buttons(**action).iter().any(|button| {
+                    if action.is_held() {
+                        gamepad.is_button_down(*button)
+                    } else {
+                        gamepad.is_button_pressed(*button)
+                    }
+                })
+            })
+            .map(|action| action.to_input())
+            .collect();
+
+        // the tilt starts over from nothing at the edge of the dead zone, so the
+        // slowest movement isn't a sudden jump
+        let (x, y) = gamepad.get_stick(self.stick);
+        let tilt = x.hypot(y);
+        if tilt > self.dead_zone {
+            let magnitude = ((tilt - self.dead_zone) / (1. - self.dead_zone)).min(1.);
+            events.push(InputEvent::Move {
+                x: x / tilt * magnitude,
+                y: y / tilt * magnitude,
+            });
+        }
+        events
+    }
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        let bindings = [
+            (Action::Up, Key::Char('W')),
+            (Action::Up, Key::Up),
+            (Action::Down, Key::Char('S')),
+            (Action::Down, Key::Down),
+            (Action::Left, Key::Char('A')),
+            (Action::Left, Key::Left),
+            (Action::Right, Key::Char('D')),
+            (Action::Right, Key::Right),
+            (Action::Interact, Key::Char('E')),
+            (Action::Interact, Key::Enter),
+            (Action::Attack, Key::Space),
+            (Action::Inventory, Key::Char('I')),
+            (Action::Pause, Key::Escape),
+        ];
+        let buttons = [
+            (Action::Up, GamepadButton::DPadUp),
+            (Action::Down, GamepadButton::DPadDown),
+            (Action::Left, GamepadButton::DPadLeft),
+            (Action::Right, GamepadButton::DPadRight),
+            (Action::Interact, GamepadButton::A),
+            (Action::Attack, GamepadButton::X),
+            (Action::Inventory, GamepadButton::Y),
+            (Action::Pause, GamepadButton::B),
+            (Action::Pause, GamepadButton::Start),
+        ];
+        Self {
+            bindings: bindings
+                .iter()
+                .map(|(action, key)| (*action, Binding::new(*key)))
+                .collect(),
+            buttons: buttons.to_vec(),
+            ..InputMap::empty()
+        }
+    }
+}
+
+/// [`InputMapError`] represents a problem binding a key.
+#[derive(Debug, PartialEq)]
+pub enum InputMapError {
+    /// [`InputMapError::Conflict`] means a `binding` can't be bound to an `action`,
+    /// since it is already `bound` to another one.
+    Conflict {
+        binding: Binding,
+        action: Action,
+        bound: Action,
+    },
+    /// [`InputMapError::ButtonConflict`] means a `button` can't be bound to an
+    /// `action`, since it is already `bound` to another one.
+    ButtonConflict {
+        button: GamepadButton,
+        action: Action,
+        bound: Action,
+    },
+}
+
+impl fmt::Display for InputMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputMapError::Conflict {
+                binding,
+                action,
+                bound,
+            } => write!(
+                f,
+                "`{binding}` can't be bound to `{action}`, since it is already bound to `{bound}`"
+            ),
+            InputMapError::ButtonConflict {
+                button,
+                action,
+                bound,
+            } => write!(
+                f,
+                "`{button}` can't be bound to `{action}`, since it is already bound to `{bound}`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InputMapError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::gamepad::GamepadSnapshot;
+
+    // a keyboard with one key pressed this frame, alongside some modifiers
+    struct TestKeyboard {
+        key: Key,
+        modifiers: Modifiers,
+    }
+
+    impl KeyboardState for TestKeyboard {
+        fn is_key_down(&self, key: Key) -> bool {
+            self.key == key
+        }
+
+        fn is_key_pressed(&self, key: Key) -> bool {
+            self.key == key
+        }
+
+        fn get_modifiers(&self) -> Modifiers {
+            self.modifiers
+        }
+    }
+
+    const SHIFT: Modifiers = Modifiers {
+        shift: true,
+        ctrl: false,
+        alt: false,
+    };
+
+    // the length of the direction of an `InputEvent::Move`
+    fn get_move_length(events: &[InputEvent]) -> Option<f32> {
+        events.iter().find_map(|event| match event {
+            InputEvent::Move { x, y } => Some(x.hypot(*y)),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn shift_is_ignored_by_bindings_without_modifiers() {
+        let mut input_map = InputMap::default();
+        let keyboard = TestKeyboard {
+            key: Key::Char('W'),
+            modifiers: SHIFT,
+        };
+        assert_eq!(input_map.map_input(&keyboard), vec![InputEvent::Up]);
+
+        // unless the key is bound with shift too, in which case that binding wins
+        let shift_w = Binding {
+            key: Key::Char('W'),
+            modifiers: SHIFT,
+        };
+        input_map.bind(Action::Attack, shift_w).unwrap();
+        assert_eq!(input_map.map_input(&keyboard), vec![InputEvent::Attack]);
+    }
+
+    #[test]
+    fn other_modifiers_are_not_ignored() {
+        let input_map = InputMap::default();
+        let keyboard = TestKeyboard {
+            key: Key::Char('W'),
+            modifiers: Modifiers {
+                ctrl: true,
+                ..Default::default()
+            },
+        };
+        assert!(input_map.map_input(&keyboard).is_empty());
+    }
+
+    #[test]
+    fn stick_within_dead_zone_does_not_move() {
+        let input_map = InputMap::default();
+        let gamepad = GamepadSnapshot {
+            left_stick: (0.1, -0.15),
+            ..Default::default()
+        };
+        assert!(input_map.map_gamepad(&gamepad).is_empty());
+    }
+
+    #[test]
+    fn stick_magnitude_starts_over_past_dead_zone() {
+        let input_map = InputMap::default();
+        let gamepad = GamepadSnapshot {
+            left_stick: (0.6, 0.),
+            ..Default::default()
+        };
+        // halfway between the dead zone and a full tilt moves at half speed
+        let length = get_move_length(&input_map.map_gamepad(&gamepad)).unwrap();
+        assert!((length - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn full_diagonal_tilt_is_no_longer_than_one() {
+        let input_map = InputMap::default();
+        let gamepad = GamepadSnapshot {
+            left_stick: (1., 1.),
+            ..Default::default()
+        };
+        let length = get_move_length(&input_map.map_gamepad(&gamepad)).unwrap();
+        assert!((length - 1.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn only_the_chosen_stick_moves() {
+        let mut input_map = InputMap::default();
+        let gamepad = GamepadSnapshot {
+            right_stick: (0., 1.),
+            ..Default::default()
+        };
+        assert!(input_map.map_gamepad(&gamepad).is_empty());
+        input_map.stick = Stick::Right;
+        assert_eq!(
+            input_map.map_gamepad(&gamepad),
+            vec![InputEvent::Move { x: 0., y: 1. }]
+        );
+    }
+
+    #[test]
+    fn buttons_map_to_actions() {
+        let input_map = InputMap::default();
+        let gamepad = GamepadSnapshot {
+            down: vec![GamepadButton::DPadLeft, GamepadButton::A],
+            pressed: vec![GamepadButton::X, GamepadButton::Start],
+            ..Default::default()
+        };
+        // held movement repeats while down, while other actions only fire when pressed
+        assert_eq!(
+            input_map.map_gamepad(&gamepad),
+            vec![InputEvent::Left, InputEvent::Attack, InputEvent::Quit]
+        );
+    }
+
+    #[test]
+    fn binding_a_taken_button_is_a_conflict() {
+        let mut input_map = InputMap::default();
+        assert!(matches!(
+            input_map.bind_button(Action::Attack, GamepadButton::A),
+            Err(InputMapError::ButtonConflict {
+                button: GamepadButton::A,
+                action: Action::Attack,
+                bound: Action::Interact,
+            })
+        ));
+        assert_eq!(
+            input_map.get_button_action(GamepadButton::A),
+            Some(Action::Interact)
+        );
+        assert!(
+            input_map
+                .bind_button(Action::Interact, GamepadButton::A)
+                .is_ok()
+        );
+        assert!(
+            input_map
+                .bind_button(Action::Attack, GamepadButton::RightBumper)
+                .is_ok()
+        );
+        assert_eq!(
+            input_map.get_buttons(Action::Attack),
+            vec![GamepadButton::X, GamepadButton::RightBumper]
+        );
+    }
+
+    #[test]
+    fn pressed_keys_are_reported_with_their_modifiers() {
+        let keyboard = TestKeyboard {
+            key: Key::Char('7'),
+            modifiers: SHIFT,
+        };
+        assert_eq!(
+            Binding::get_pressed(&keyboard),
+            Some(Binding {
+                key: Key::Char('7'),
+                modifiers: SHIFT
+            })
+        );
+        assert_eq!(Key::all().count(), 9 + 26 + 10);
+    }
+}
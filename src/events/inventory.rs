@@ -0,0 +1,31 @@
+use super::{event::Event, input::InputEvent, mouse::MouseButton};
+use crate::{items::inventory::Inventory, ui};
+
+pub fn handle_inventory_input(event: InputEvent) -> Event {
+    match event {
+        InputEvent::Quit | InputEvent::Inventory => Event::Back,
+        InputEvent::Left => Event::MoveCursor { x: -1, y: 0 },
+        InputEvent::Right => Event::MoveCursor { x: 1, y: 0 },
+        InputEvent::Up => Event::MoveCursor { x: 0, y: -1 },
+        InputEvent::Down => Event::MoveCursor { x: 0, y: 1 },
+        InputEvent::Interact => Event::Confirm,
+        InputEvent::MouseClick {
+            x,
+            y,
+            button: MouseButton::Left,
+        } => Event::LeftClicked { x, y },
+        _ => Event::Nothing,
+    }
+}
+
+pub fn handle_inventory_click(inventory: &Inventory, mouse_x: f32, mouse_y: f32) -> Event {
+    if let Some(slot) = ui::inventory::get_slot_at(mouse_x, mouse_y, inventory.capacity()) {
+        Event::Select(slot)
+    } else if let Some(slot) =
+        ui::inventory::get_equip_slot_at(mouse_x, mouse_y, inventory.capacity())
+    {
+        Event::Unequip(slot)
+    } else {
+        Event::Nothing
+    }
+}
@@ -1,4 +1,5 @@
 /// [`MouseButton`] represents the different mouse buttons the user has.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MouseButton {
     Left,
     Right,
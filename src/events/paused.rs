@@ -0,0 +1,25 @@
+use super::{event::Event, input::InputEvent, mouse::MouseButton};
+use crate::ui;
+
+pub fn handle_paused_input(event: InputEvent) -> Event {
+    match event {
+        InputEvent::Quit => Event::Back,
+        InputEvent::Up => Event::MoveCursor { x: 0, y: -1 },
+        InputEvent::Down => Event::MoveCursor { x: 0, y: 1 },
+        InputEvent::Interact => Event::Confirm,
+        InputEvent::MouseClick {
+            x,
+            y,
+            button: MouseButton::Left,
+        } => Event::LeftClicked { x, y },
+        InputEvent::MouseMove { x, y } => Event::Hovered { x, y },
+        _ => Event::Nothing,
+    }
+}
+
+pub fn handle_paused_click(mouse_x: f32, mouse_y: f32) -> Event {
+    match ui::pause::get_pause_option_at(mouse_x, mouse_y) {
+        Some(index) => Event::Select(index),
+        None => Event::Nothing,
+    }
+}
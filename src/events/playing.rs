@@ -6,11 +6,15 @@ use crate::{
 
 pub fn handle_playing_input(event: InputEvent) -> Event {
     match event {
-        InputEvent::Quit => Event::Quit,
+        InputEvent::Quit => Event::Pause,
         InputEvent::Left => Event::MovePlayerBackward,
         InputEvent::Right => Event::MovePlayerForward,
         InputEvent::Up => Event::MovePlayerUp,
         InputEvent::Down => Event::MovePlayerDown,
+        InputEvent::Move { x, y } => Event::MovePlayer { x, y },
+        InputEvent::Interact => Event::Interact,
+        InputEvent::Attack => Event::Attack,
+        InputEvent::Inventory => Event::OpenInventory,
         InputEvent::MouseClick {
             x,
             y,
@@ -1,9 +1,25 @@
-use super::{event::Event, input::InputEvent};
+use super::{event::Event, input::InputEvent, mouse::MouseButton};
+use crate::ui;
 
 pub fn handle_title_screen_input(event: InputEvent) -> Event {
     match event {
-        InputEvent::Interact => Event::Continue,
-        InputEvent::Quit => Event::Quit,
+        InputEvent::Up => Event::MoveCursor { x: 0, y: -1 },
+        InputEvent::Down => Event::MoveCursor { x: 0, y: 1 },
+        InputEvent::Interact => Event::Confirm,
+        InputEvent::Quit => Event::Back,
+        InputEvent::MouseClick {
+            x,
+            y,
+            button: MouseButton::Left,
+        } => Event::LeftClicked { x, y },
+        InputEvent::MouseMove { x, y } => Event::Hovered { x, y },
         _ => Event::Nothing,
     }
 }
+
+pub fn handle_title_screen_click(mouse_x: f32, mouse_y: f32, columns: f32, rows: f32) -> Event {
+    match ui::main_menu::get_title_option_at(mouse_x, mouse_y, columns, rows) {
+        Some(index) => Event::Select(index),
+        None => Event::Nothing,
+    }
+}
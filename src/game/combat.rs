@@ -0,0 +1,139 @@
+/// [`ATTACK_REACH`] is how many tiles in front of the player an attack lands.
+pub const ATTACK_REACH: f32 = 1.;
+
+/// [`ATTACK_COOLDOWN`] is how many seconds the player waits between attacks.
+pub const ATTACK_COOLDOWN: f32 = 0.4;
+
+/// [`SWING_TIME`] is how many seconds an attack is shown for.
+pub const SWING_TIME: f32 = 0.15;
+
+// how many seconds something can't be hurt again after a hit
+const INVULNERABLE_TIME: f32 = 0.6;
+// how many tiles per second a hit pushes something away, and how quickly it slows
+const KNOCKBACK_SPEED: f32 = 10.;
+const KNOCKBACK_FRICTION: f32 = 40.;
+// how many seconds a blink lasts while invulnerable
+const BLINK_TIME: f32 = 0.1;
+
+/// [`HitRecovery`] tracks how something recovers from being hit: it is pushed back
+/// by the hit, and can't be hurt again for a moment.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HitRecovery {
+    /// [`HitRecovery::knockback`] is how many tiles per second a hit is pushing
+    /// something on each axis.
+    pub knockback: (f32, f32),
+    invulnerable_timer: f32,
+}
+
+impl HitRecovery {
+    /// [`HitRecovery::hit`] starts recovering from a hit that came `from` an (`x`,
+    /// `y`) coordinate towards something centered at `to`.
+    pub fn hit(&mut self, from: (f32, f32), to: (f32, f32)) {
+        let (difference_x, difference_y) = (to.0 - from.0, to.1 - from.1);
+        let distance = difference_x.hypot(difference_y);
+        self.knockback = if distance == 0. {
+            (0., 0.)
+        } else {
+            (
+                difference_x / distance * KNOCKBACK_SPEED,
+                difference_y / distance * KNOCKBACK_SPEED,
+            )
+        };
+        self.invulnerable_timer = INVULNERABLE_TIME;
+    }
+
+    /// [`HitRecovery::update`] will be called every frame to wear off a hit, and
+    /// returns the (`x`, `y`) distance the knockback moves something this frame.
+    pub fn update(&mut self, frame_time: f32) -> (f32, f32) {
+        self.invulnerable_timer = (self.invulnerable_timer - frame_time).max(0.);
+        let delta = (self.knockback.0 * frame_time, self.knockback.1 * frame_time);
+        let speed = self.knockback.0.hypot(self.knockback.1);
+        let slowed = (speed - KNOCKBACK_FRICTION * frame_time).max(0.);
+        self.knockback = if speed == 0. {
+            (0., 0.)
+        } else {
+            (
+                self.knockback.0 / speed * slowed,
+                self.knockback.1 / speed * slowed,
+            )
+        };
+        delta
+    }
+
+    /// [`HitRecovery::is_invulnerable`] returns `true` while something can't be hurt.
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_timer > 0.
+    }
+
+    /// [`HitRecovery::is_hidden`] returns `true` on the frames something blinks out
+    /// while it is invulnerable.
+    pub fn is_hidden(&self) -> bool {
+        self.is_invulnerable() && (self.invulnerable_timer / BLINK_TIME) as u32 % 2 == 1
+    }
+}
+
+/// [`get_damage_taken`] returns how much of an attack with `damage` gets through an
+/// `armor_rating`. A hit always does at least one damage.
+pub fn get_damage_taken(damage: u32, armor_rating: u32) -> u32 {
+    damage.saturating_sub(armor_rating / 2).max(1)
+}
+
+/// [`get_gap`] returns the distance in tiles between the edges of two areas, each
+/// given as (`x`, `y`, `width`, `height`), which is `0.` if they touch or overlap.
+pub fn get_gap(a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)) -> f32 {
+    let gap_x = (b.0 - (a.0 + a.2)).max(a.0 - (b.0 + b.2)).max(0.);
+    let gap_y = (b.1 - (a.1 + a.3)).max(a.1 - (b.1 + b.3)).max(0.);
+    gap_x.hypot(gap_y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn armor_absorbs_half_its_rating_but_never_the_whole_hit() {
+        assert_eq!(get_damage_taken(10, 4), 8);
+        assert_eq!(get_damage_taken(5, 1), 5);
+        assert_eq!(get_damage_taken(3, 10), 1);
+        assert_eq!(get_damage_taken(0, 0), 1);
+    }
+
+    #[test]
+    fn hits_push_away_from_where_they_came_from() {
+        let mut recovery = HitRecovery::default();
+        recovery.hit((0., 0.), (3., 4.));
+        assert_eq!(recovery.knockback, (6., 8.));
+        assert!(recovery.is_invulnerable());
+
+        // the push moves the full speed this frame, and slows down for the next
+        let (x, y) = recovery.update(0.1);
+        assert!((x - 0.6).abs() < 1e-5 && (y - 0.8).abs() < 1e-5);
+        let (x, y) = recovery.knockback;
+        assert!((x - 3.6).abs() < 1e-5 && (y - 4.8).abs() < 1e-5);
+
+        // a hit from the same spot can't tell which way to push
+        recovery.hit((1., 1.), (1., 1.));
+        assert_eq!(recovery.knockback, (0., 0.));
+    }
+
+    #[test]
+    fn hits_wear_off() {
+        let mut recovery = HitRecovery::default();
+        recovery.hit((0., 0.), (1., 0.));
+        for _ in 0..10 {
+            recovery.update(0.1);
+        }
+        assert_eq!(recovery.knockback, (0., 0.));
+        assert_eq!(recovery.update(0.1), (0., 0.));
+        assert!(!recovery.is_invulnerable());
+        assert!(!recovery.is_hidden());
+    }
+
+    #[test]
+    fn gaps_are_measured_between_edges() {
+        assert_eq!(get_gap((0., 0., 1., 1.), (1., 0., 1., 1.)), 0.);
+        assert_eq!(get_gap((0., 0., 2., 2.), (1., 1., 1., 1.)), 0.);
+        assert_eq!(get_gap((0., 0., 1., 1.), (4., 5., 1., 1.)), 5.);
+        assert_eq!(get_gap((4., 5., 1., 1.), (0., 0., 1., 1.)), 5.);
+    }
+}
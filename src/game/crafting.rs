@@ -0,0 +1,53 @@
+use crate::{
+    items::{catalog::ItemCatalog, inventory::Inventory, recipe::RecipeBook},
+    maps::building_type::BuildingType,
+};
+
+/// [`CraftingMenu`] holds the state of the crafting screen while it is open.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CraftingMenu {
+    /// [`CraftingMenu::station`] is [`Some`] [`BuildingType`] being crafted at, or
+    /// [`None`] when crafting by hand.
+    pub station: Option<BuildingType>,
+    /// [`CraftingMenu::selected`] is the index of the selected recipe.
+    pub selected: usize,
+    /// [`CraftingMenu::message`] is the result of the last crafting attempt.
+    pub message: Option<String>,
+}
+
+impl CraftingMenu {
+    /// [`CraftingMenu::new`] creates a new [`CraftingMenu`] for a `station`.
+    pub fn new(station: Option<BuildingType>) -> Self {
+        Self {
+            station,
+            ..Default::default()
+        }
+    }
+
+    /// [`CraftingMenu::move_cursor`] moves the selected recipe by `y` rows, staying
+    /// within the `recipe_count`.
+    pub fn move_cursor(&mut self, y: i32, recipe_count: usize) {
+        let last = recipe_count.saturating_sub(1) as i32;
+        self.selected = (self.selected as i32 + y).clamp(0, last) as usize;
+    }
+
+    /// [`CraftingMenu::craft_selected`] will attempt to craft the selected recipe
+    /// from a [`RecipeBook`] with the items in an `inventory`, and leave a message
+    /// describing the result. It returns `true` if something was crafted.
+    pub fn craft_selected(
+        &mut self,
+        recipes: &RecipeBook,
+        items: &ItemCatalog,
+        inventory: &mut Inventory,
+    ) -> bool {
+        let Some(recipe) = recipes.recipes().get(self.selected) else {
+            return false;
+        };
+        let result = recipe.craft(inventory, items, self.station);
+        self.message = Some(match &result {
+            Ok(()) => format!("Crafted {}", recipe.name),
+            Err(error) => error.to_string(),
+        });
+        result.is_ok()
+    }
+}
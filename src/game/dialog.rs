@@ -0,0 +1,589 @@
+use super::player::Player;
+use crate::{
+    data::record::{self, DataError, Record},
+    items::{catalog::ItemCatalog, currency::Price},
+    maps::building_type::BuildingType,
+};
+use std::{fmt, str::FromStr};
+
+/// [`DIALOGS_PATH`] is the data file every [`Dialog`] is defined in.
+pub const DIALOGS_PATH: &str = "assets/dialogs.txt";
+
+/// [`END_NODE`] is the node id that a [`DialogChoice`] or [`DialogNode::next`] goes
+/// to in order to end a [`Dialog`].
+pub const END_NODE: &str = "end";
+
+// how many characters of text are revealed every second
+const REVEAL_SPEED: f32 = 40.;
+
+/// [`Condition`] is something that must be true for a [`DialogChoice`] to be shown.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    /// [`Condition::HasItem`] needs at least `quantity` of an item in the inventory.
+    HasItem { item_id: String, quantity: u32 },
+    /// [`Condition::MinLevel`] needs the [`Player`] to be at least a level.
+    MinLevel(u32),
+    /// [`Condition::BelowLevel`] needs the [`Player`] to be under a level.
+    BelowLevel(u32),
+    /// [`Condition::HasMoney`] needs the [`Player`] to have at least an amount of copper.
+    HasMoney(u64),
+    /// [`Condition::HasFlag`] needs a flag to be set.
+    HasFlag(String),
+    /// [`Condition::NotFlag`] needs a flag to not be set.
+    NotFlag(String),
+}
+
+impl Condition {
+    /// [`Condition::is_met`] returns `true` if a [`Condition`] holds for a [`Player`]
+    /// and the `flags` that have been set.
+    pub fn is_met(&self, player: &Player, flags: &[String]) -> bool {
+        match self {
+            Condition::HasItem { item_id, quantity } => {
+                player.inventory.count(item_id) >= *quantity
+            }
+            Condition::MinLevel(level) => player.stats.level >= *level,
+            Condition::BelowLevel(level) => player.stats.level < *level,
+            Condition::HasMoney(copper) => player.wallet.can_afford(Price::from_copper(*copper)),
+            Condition::HasFlag(flag) => flags.contains(flag),
+            Condition::NotFlag(flag) => !flags.contains(flag),
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            ["has_item", item_id, quantity @ ..] => Ok(Condition::HasItem {
+                item_id: (*item_id).to_owned(),
+                quantity: parse_quantity(quantity)?,
+            }),
+            ["min_level", level] => Ok(Condition::MinLevel(level.parse().map_err(|_| ())?)),
+            ["below_level", level] => Ok(Condition::BelowLevel(level.parse().map_err(|_| ())?)),
+            ["has_money", copper] => Ok(Condition::HasMoney(copper.parse().map_err(|_| ())?)),
+            ["flag", flag] => Ok(Condition::HasFlag((*flag).to_owned())),
+            ["not_flag", flag] => Ok(Condition::NotFlag((*flag).to_owned())),
+            _ => Err(()),
+        }
+    }
+}
+
+/// [`Effect`] is something that happens when a [`DialogNode`] is reached.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Effect {
+    /// [`Effect::GiveItem`] adds a `quantity` of an item to the inventory.
+    GiveItem { item_id: String, quantity: u32 },
+    /// [`Effect::TakeItem`] removes a `quantity` of an item from the inventory.
+    TakeItem { item_id: String, quantity: u32 },
+    /// [`Effect::GiveMoney`] adds an amount of copper to the wallet.
+    GiveMoney(u64),
+    /// [`Effect::TakeMoney`] spends an amount of copper from the wallet.
+    TakeMoney(u64),
+    /// [`Effect::GiveXp`] gives the [`Player`] an amount of experience.
+    GiveXp(u32),
+    /// [`Effect::SetFlag`] sets a flag.
+    SetFlag(String),
+    /// [`Effect::ClearFlag`] clears a flag.
+    ClearFlag(String),
+}
+
+impl FromStr for Effect {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            ["give_item", item_id, quantity @ ..] => Ok(Effect::GiveItem {
+                item_id: (*item_id).to_owned(),
+                quantity: parse_quantity(quantity)?,
+            }),
+            ["take_item", item_id, quantity @ ..] => Ok(Effect::TakeItem {
+                item_id: (*item_id).to_owned(),
+                quantity: parse_quantity(quantity)?,
+            }),
+            ["give_money", copper] => Ok(Effect::GiveMoney(copper.parse().map_err(|_| ())?)),
+            ["take_money", copper] => Ok(Effect::TakeMoney(copper.parse().map_err(|_| ())?)),
+            ["give_xp", amount] => Ok(Effect::GiveXp(amount.parse().map_err(|_| ())?)),
+            ["set_flag", flag] => Ok(Effect::SetFlag((*flag).to_owned())),
+            ["clear_flag", flag] => Ok(Effect::ClearFlag((*flag).to_owned())),
+            _ => Err(()),
+        }
+    }
+}
+
+// an item quantity is optional, and one if left out
+fn parse_quantity(quantity: &[&str]) -> Result<u32, ()> {
+    match quantity {
+        [] => Ok(1),
+        [quantity] => quantity.parse().map_err(|_| ()),
+        _ => Err(()),
+    }
+}
+
+/// [`DialogChoice`] is a reply the player can pick in a [`DialogNode`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DialogChoice {
+    /// [`DialogChoice::text`] is what the player says.
+    pub text: String,
+    /// [`DialogChoice::next`] is the id of the [`DialogNode`] it leads to, or
+    /// [`END_NODE`].
+    pub next: String,
+    /// [`DialogChoice::conditions`] must all be met for it to be shown.
+    pub conditions: Vec<Condition>,
+}
+
+impl FromStr for DialogChoice {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('|').map(str::trim);
+        let (Some(next), Some(text)) = (parts.next(), parts.next()) else {
+            return Err(());
+        };
+        let conditions = match parts.next() {
+            Some(conditions) => conditions
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<Vec<Condition>, ()>>()?,
+            None => Vec::new(),
+        };
+        if next.is_empty() || text.is_empty() || parts.next().is_some() {
+            return Err(());
+        }
+        Ok(Self {
+            text: text.to_owned(),
+            next: next.to_owned(),
+            conditions,
+        })
+    }
+}
+
+/// [`DialogNode`] is one line said by the speaker of a [`Dialog`], and the replies
+/// the player has to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DialogNode {
+    /// [`DialogNode::id`] identifies a [`DialogNode`] within its [`Dialog`].
+    pub id: String,
+    /// [`DialogNode::text`] is what the speaker says.
+    pub text: String,
+    /// [`DialogNode::choices`] are the replies the player can pick.
+    pub choices: Vec<DialogChoice>,
+    /// [`DialogNode::next`] is the id of the [`DialogNode`] to continue to when no
+    /// choice can be picked, where [`None`] ends the [`Dialog`].
+    pub next: Option<String>,
+    /// [`DialogNode::effects`] happen when the [`DialogNode`] is reached.
+    pub effects: Vec<Effect>,
+}
+
+impl DialogNode {
+    /// [`DialogNode::get_choices`] returns every [`DialogChoice`] whose conditions are
+    /// met for a [`Player`] and the `flags` that have been set.
+    pub fn get_choices(&self, player: &Player, flags: &[String]) -> Vec<&DialogChoice> {
+        self.choices
+            .iter()
+            .filter(|c| c.conditions.iter().all(|c| c.is_met(player, flags)))
+            .collect()
+    }
+}
+
+/// [`Dialog`] is a conversation with a speaker, made of [`DialogNode`] that branch
+/// on the choices of the player.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dialog {
+    /// [`Dialog::id`] is the stable identifier of a [`Dialog`].
+    pub id: String,
+    /// [`Dialog::speaker`] is the name shown above the text.
+    pub speaker: String,
+    /// [`Dialog::start`] is the id of the first [`DialogNode`].
+    pub start: String,
+    /// [`Dialog::building`] is [`Some`] [`BuildingType`] that starts a [`Dialog`]
+    /// when used, and [`None`] otherwise.
+    pub building: Option<BuildingType>,
+    nodes: Vec<DialogNode>,
+}
+
+impl Dialog {
+    /// [`Dialog::get_node`] returns [`Some`] [`DialogNode`] with `id`, and [`None`]
+    /// otherwise.
+    pub fn get_node(&self, id: &str) -> Option<&DialogNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+}
+
+/// [`DialogBook`] holds every [`Dialog`] in the game, by [`Dialog::id`].
+#[derive(Clone, Debug, Default)]
+pub struct DialogBook {
+    dialogs: Vec<Dialog>,
+}
+
+impl DialogBook {
+    /// [`DialogBook::load`] reads and parses the dialog data file at `path`, checking
+    /// that every item it mentions is in an [`ItemCatalog`].
+    pub fn load(path: &str, items: &ItemCatalog) -> Result<Self, DialogError> {
+        let records = record::read_records(path)?;
+        Self::from_records(&records, items)
+    }
+
+    /// [`DialogBook::parse`] parses the `source` of a dialog data file, where each
+    /// [`Dialog`] is a `[dialog id]` header and each of its [`DialogNode`] is a
+    /// `[node dialog_id.node_id]` header, both followed by their fields.
+    pub fn parse(source: &str, items: &ItemCatalog) -> Result<Self, DialogError> {
+        let records = record::parse_records(source)?;
+        Self::from_records(&records, items)
+    }
+
+    fn from_records(records: &[Record], items: &ItemCatalog) -> Result<Self, DialogError> {
+        let mut book = DialogBook::default();
+        for dialog_record in records.iter().filter(|r| r.kind == "dialog") {
+            if book.get(&dialog_record.id).is_some() {
+                return Err(DialogError::DuplicateId {
+                    id: dialog_record.id.clone(),
+                    line: dialog_record.line,
+                });
+            }
+            book.dialogs.push(Dialog {
+                id: dialog_record.id.clone(),
+                speaker: dialog_record.require("speaker")?.to_owned(),
+                start: dialog_record.require("start")?.to_owned(),
+                building: dialog_record.parse_optional("building")?,
+                nodes: Vec::new(),
+            });
+        }
+
+        for node_record in records.iter().filter(|r| r.kind == "node") {
+            let line = node_record.line;
+            let Some((dialog_id, node_id)) = node_record.id.split_once('.') else {
+                return Err(DataError::Syntax {
+                    line,
+                    message: "a node header must look like `[node dialog_id.node_id]`".to_owned(),
+                }
+                .into());
+            };
+            let node = parse_node(node_record, node_id)?;
+            for item_id in get_item_ids(&node) {
+                if items.get(item_id).is_none() {
+                    return Err(DialogError::UnknownItem {
+                        id: node_record.id.clone(),
+                        item_id: item_id.to_owned(),
+                        line,
+                    });
+                }
+            }
+            let Some(dialog) = book.dialogs.iter_mut().find(|d| d.id == dialog_id) else {
+                return Err(DialogError::UnknownDialog {
+                    id: dialog_id.to_owned(),
+                    line,
+                });
+            };
+            if dialog.get_node(node_id).is_some() {
+                return Err(DialogError::DuplicateId {
+                    id: node_record.id.clone(),
+                    line,
+                });
+            }
+            dialog.nodes.push(node);
+        }
+
+        // every node a dialog can go to has to exist
+        for dialog in &book.dialogs {
+            let targets = std::iter::once(&dialog.start).chain(
+                dialog
+                    .nodes
+                    .iter()
+                    .flat_map(|n| n.choices.iter().map(|c| &c.next).chain(n.next.as_ref())),
+            );
+            for target in targets {
+                if target != END_NODE && dialog.get_node(target).is_none() {
+                    return Err(DialogError::UnknownNode {
+                        id: dialog.id.clone(),
+                        node_id: target.clone(),
+                    });
+                }
+            }
+        }
+        Ok(book)
+    }
+
+    /// [`DialogBook::get`] returns [`Some`] [`Dialog`] defined with `id`, and [`None`]
+    /// otherwise.
+    pub fn get(&self, id: &str) -> Option<&Dialog> {
+        self.dialogs.iter().find(|d| d.id == id)
+    }
+
+    /// [`DialogBook::get_for_building`] returns [`Some`] [`Dialog`] started by using
+    /// a [`BuildingType`], and [`None`] otherwise.
+    pub fn get_for_building(&self, building_type: BuildingType) -> Option<&Dialog> {
+        self.dialogs
+            .iter()
+            .find(|d| d.building == Some(building_type))
+    }
+}
+
+fn parse_node(node_record: &Record, node_id: &str) -> Result<DialogNode, DataError> {
+    let choices = node_record
+        .get_all("choice")
+        .into_iter()
+        .map(|(value, line)| record::parse_value(value, "choice", line))
+        .collect::<Result<Vec<DialogChoice>, DataError>>()?;
+    let effects = node_record
+        .get_all("effect")
+        .into_iter()
+        .map(|(value, line)| record::parse_value(value, "effect", line))
+        .collect::<Result<Vec<Effect>, DataError>>()?;
+    Ok(DialogNode {
+        id: node_id.to_owned(),
+        text: node_record.require("text")?.to_owned(),
+        choices,
+        next: node_record.get("next").map(str::to_owned),
+        effects,
+    })
+}
+
+// every item id mentioned by the conditions and effects of a node
+fn get_item_ids(node: &DialogNode) -> impl Iterator<Item = &str> {
+    let condition_ids = node
+        .choices
+        .iter()
+        .flat_map(|c| &c.conditions)
+        .filter_map(|c| match c {
+            Condition::HasItem { item_id, .. } => Some(item_id.as_str()),
+            _ => None,
+        });
+    let effect_ids = node.effects.iter().filter_map(|e| match e {
+        Effect::GiveItem { item_id, .. } | Effect::TakeItem { item_id, .. } => {
+            Some(item_id.as_str())
+        }
+        _ => None,
+    });
+    condition_ids.chain(effect_ids)
+}
+
+/// [`Conversation`] holds the state of a [`Dialog`] while it is on screen.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Conversation {
+    /// [`Conversation::dialog_id`] is the id of the [`Dialog`] being had.
+    pub dialog_id: String,
+    /// [`Conversation::node_id`] is the id of the [`DialogNode`] being shown.
+    pub node_id: String,
+    /// [`Conversation::selected`] is the index of the selected [`DialogChoice`].
+    pub selected: usize,
+    revealed: f32,
+}
+
+impl Conversation {
+    /// [`Conversation::new`] creates a new [`Conversation`] showing a [`DialogNode`]
+    /// of a [`Dialog`] by their ids.
+    pub fn new(dialog_id: &str, node_id: &str) -> Self {
+        Self {
+            dialog_id: dialog_id.to_owned(),
+            node_id: node_id.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// [`Conversation::update`] will be called every frame to reveal more of the
+    /// text, like it is being typed.
+    pub fn update(&mut self, frame_time: f32) {
+        self.revealed += REVEAL_SPEED * frame_time;
+    }
+
+    /// [`Conversation::get_revealed`] returns how many characters of the text have
+    /// been revealed so far.
+    pub fn get_revealed(&self) -> usize {
+        self.revealed as usize
+    }
+
+    /// [`Conversation::is_revealed`] returns `true` once all of a `text` is revealed.
+    pub fn is_revealed(&self, text: &str) -> bool {
+        self.get_revealed() >= text.chars().count()
+    }
+
+    /// [`Conversation::reveal`] will reveal all of a `text` at once.
+    pub fn reveal(&mut self, text: &str) {
+        self.revealed = self.revealed.max(text.chars().count() as f32);
+    }
+
+    /// [`Conversation::move_cursor`] moves the selected choice by `y` rows, staying
+    /// within the `choice_count`.
+    pub fn move_cursor(&mut self, y: i32, choice_count: usize) {
+        let last = choice_count.saturating_sub(1) as i32;
+        self.selected = (self.selected as i32 + y).clamp(0, last) as usize;
+    }
+}
+
+/// [`DialogError`] represents a problem loading a [`DialogBook`].
+#[derive(Debug)]
+pub enum DialogError {
+    /// [`DialogError::Data`] means the dialog data file couldn't be read or parsed.
+    Data(DataError),
+    /// [`DialogError::DuplicateId`] means two [`Dialog`] or two [`DialogNode`] in the
+    /// same [`Dialog`] were defined with the same `id`.
+    DuplicateId { id: String, line: usize },
+    /// [`DialogError::UnknownDialog`] means a [`DialogNode`] belongs to a [`Dialog`]
+    /// that isn't defined.
+    UnknownDialog { id: String, line: usize },
+    /// [`DialogError::UnknownNode`] means a [`Dialog`] goes to a [`DialogNode`] that
+    /// isn't defined.
+    UnknownNode { id: String, node_id: String },
+    /// [`DialogError::UnknownItem`] means a [`DialogNode`] mentions an item that isn't
+    /// defined.
+    UnknownItem {
+        id: String,
+        item_id: String,
+        line: usize,
+    },
+}
+
+impl From<DataError> for DialogError {
+    fn from(error: DataError) -> Self {
+        DialogError::Data(error)
+    }
+}
+
+impl fmt::Display for DialogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialogError::Data(error) => write!(f, "dialog data error: {error}"),
+            DialogError::DuplicateId { id, line } => {
+                write!(f, "line {line}: dialog `{id}` is defined more than once")
+            }
+            DialogError::UnknownDialog { id, line } => {
+                write!(f, "line {line}: dialog `{id}` isn't defined")
+            }
+            DialogError::UnknownNode { id, node_id } => {
+                write!(
+                    f,
+                    "dialog `{id}` goes to node `{node_id}`, which isn't defined"
+                )
+            }
+            DialogError::UnknownItem { id, item_id, line } => {
+                write!(f, "line {line}: node `{id}` uses unknown item `{item_id}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DialogError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: &str = "[item iron_ore]\nname = Iron Ore\nsprite = icon_ore\ntype = material\n";
+
+    const DIALOGS: &str = "\
+[dialog tinker]
+speaker = Tinker
+start = greeting
+
+[node tinker.greeting]
+text = Hello.
+choice = ore | Ore? | has_item iron_ore 3, not_flag paid
+choice = end | Bye.
+
+[node tinker.ore]
+text = Thanks.
+effect = take_item iron_ore 3
+effect = set_flag paid
+next = end
+";
+
+    #[test]
+    fn conditions_and_effects_are_parsed() {
+        assert_eq!(
+            "has_item iron_ore".parse(),
+            Ok(Condition::HasItem {
+                item_id: "iron_ore".to_owned(),
+                quantity: 1
+            })
+        );
+        assert_eq!("has_money 250".parse(), Ok(Condition::HasMoney(250)));
+        assert_eq!(
+            "not_flag paid".parse(),
+            Ok(Condition::NotFlag("paid".to_owned()))
+        );
+        assert_eq!("min_level".parse::<Condition>(), Err(()));
+        assert_eq!("has_item iron_ore 1 2".parse::<Condition>(), Err(()));
+
+        assert_eq!(
+            "take_item iron_ore 3".parse(),
+            Ok(Effect::TakeItem {
+                item_id: "iron_ore".to_owned(),
+                quantity: 3
+            })
+        );
+        assert_eq!("give_xp 10".parse(), Ok(Effect::GiveXp(10)));
+        assert_eq!("give_money lots".parse::<Effect>(), Err(()));
+        assert_eq!("teleport home".parse::<Effect>(), Err(()));
+    }
+
+    #[test]
+    fn choices_are_parsed_with_their_conditions() {
+        let choice: DialogChoice = "ore | Ore? | min_level 2, flag met".parse().unwrap();
+        assert_eq!(choice.next, "ore");
+        assert_eq!(choice.text, "Ore?");
+        assert_eq!(
+            choice.conditions,
+            vec![Condition::MinLevel(2), Condition::HasFlag("met".to_owned())]
+        );
+        assert_eq!("ore".parse::<DialogChoice>(), Err(()));
+        assert_eq!(
+            "ore | Ore? | min_level 2 | extra".parse::<DialogChoice>(),
+            Err(())
+        );
+    }
+
+    #[test]
+    fn choices_are_only_shown_when_their_conditions_are_met() {
+        let items = ItemCatalog::parse(ITEMS).unwrap();
+        let book = DialogBook::parse(DIALOGS, &items).unwrap();
+        let greeting = book.get("tinker").unwrap().get_node("greeting").unwrap();
+        let mut player = Player::new(0., 0.);
+        assert_eq!(greeting.get_choices(&player, &[]).len(), 1);
+
+        player.inventory.add_item(items.get("iron_ore").unwrap(), 3);
+        assert_eq!(greeting.get_choices(&player, &[]).len(), 2);
+        assert_eq!(greeting.get_choices(&player, &["paid".to_owned()]).len(), 1);
+    }
+
+    #[test]
+    fn dialogs_going_to_missing_nodes_are_rejected() {
+        let items = ItemCatalog::parse(ITEMS).unwrap();
+        let source = DIALOGS.replace("next = end", "next = farewell");
+        assert!(matches!(
+            DialogBook::parse(&source, &items),
+            Err(DialogError::UnknownNode { node_id, .. }) if node_id == "farewell"
+        ));
+
+        let source = DIALOGS.replace("start = greeting", "start = hello");
+        assert!(matches!(
+            DialogBook::parse(&source, &items),
+            Err(DialogError::UnknownNode { node_id, .. }) if node_id == "hello"
+        ));
+
+        let source = DIALOGS.replace("[node tinker.ore]", "[node smith.ore]");
+        assert!(matches!(
+            DialogBook::parse(&source, &items),
+            Err(DialogError::UnknownDialog { id, line: 10 }) if id == "smith"
+        ));
+    }
+
+    #[test]
+    fn dialogs_mentioning_unknown_items_are_rejected() {
+        let items = ItemCatalog::parse(ITEMS).unwrap();
+        let source = DIALOGS.replace("take_item iron_ore", "take_item gold_ore");
+        assert!(matches!(
+            DialogBook::parse(&source, &items),
+            Err(DialogError::UnknownItem { item_id, line: 10, .. }) if item_id == "gold_ore"
+        ));
+
+        let source = DIALOGS.replace("effect = set_flag paid", "effect = set_flag");
+        assert!(matches!(
+            DialogBook::parse(&source, &items),
+            Err(DialogError::Data(DataError::InvalidField { field, line: 13, .. }))
+                if field == "effect"
+        ));
+    }
+}
@@ -0,0 +1,379 @@
+use super::{
+    combat::{self, HitRecovery},
+    random::Random,
+};
+use crate::{
+    data::record::{self, DataError, Record},
+    items::catalog::ItemCatalog,
+    maps::{camera::Camera, map::Map},
+    renderer::{color::Color, render::Render, sprite},
+    ui::panel,
+};
+use std::{fmt, str::FromStr};
+
+/// [`ENEMIES_PATH`] is the data file every [`Enemy`] is defined in.
+pub const ENEMIES_PATH: &str = "assets/enemies.txt";
+
+const ENEMY_WIDTH: f32 = sprite::CHARACTER_SIZE.0;
+const ENEMY_HEIGHT: f32 = sprite::CHARACTER_SIZE.1;
+const DEFAULT_ENEMY_SPEED: f32 = 2.;
+const DEFAULT_SIGHT: f32 = 6.;
+// how close an enemy has to get to the player to hit them
+const ENEMY_REACH: f32 = 0.25;
+const ENEMY_ATTACK_COOLDOWN: f32 = 1.;
+// how close an enemy has to get to where it is going to count as there
+const ARRIVAL_DISTANCE: f32 = 0.1;
+// how many seconds the remains of an enemy stay in the world
+const REMAINS_TIME: f32 = 1.5;
+
+/// [`LootDrop`] is an item an [`Enemy`] may drop when defeated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LootDrop {
+    /// [`LootDrop::item_id`] is the id of the item dropped.
+    pub item_id: String,
+    /// [`LootDrop::quantity`] is how many of the item are dropped.
+    pub quantity: u32,
+    /// [`LootDrop::chance`] is how likely the item is to drop, from `0.` to `1.`.
+    pub chance: f32,
+}
+
+impl FromStr for LootDrop {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let [item_id, quantity, chance] = words.as_slice() else {
+            return Err(());
+        };
+        let chance: f32 = chance.parse().map_err(|_| ())?;
+        if !(0. ..=1.).contains(&chance) {
+            return Err(());
+        }
+        Ok(Self {
+            item_id: (*item_id).to_owned(),
+            quantity: quantity.parse().map_err(|_| ())?,
+            chance,
+        })
+    }
+}
+
+/// [`Enemy`] is a hostile character that chases and attacks the player when they
+/// come into sight.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Enemy {
+    /// [`Enemy::id`] is the stable identifier of an [`Enemy`].
+    pub id: String,
+    /// [`Enemy::name`] is the display name of an [`Enemy`].
+    pub name: String,
+    /// [`Enemy::x`] is the horizontal world location of an [`Enemy`].
+    pub x: f32,
+    /// [`Enemy::y`] is the vertical world location of an [`Enemy`].
+    pub y: f32,
+    /// [`Enemy::speed`] is how many tiles per second an [`Enemy`] walks.
+    pub speed: f32,
+    /// [`Enemy::sprite_id`] is the id of the sprite an [`Enemy`] is drawn with.
+    pub sprite_id: String,
+    /// [`Enemy::health`] is the current health, up to [`Enemy::max_health`].
+    pub health: u32,
+    /// [`Enemy::max_health`] is the health an [`Enemy`] starts with.
+    pub max_health: u32,
+    /// [`Enemy::damage`] is how much damage each attack of an [`Enemy`] does.
+    pub damage: u32,
+    /// [`Enemy::armor_rating`] reduces the damage an [`Enemy`] takes.
+    pub armor_rating: u32,
+    /// [`Enemy::xp`] is how much experience defeating an [`Enemy`] gives.
+    pub xp: u32,
+    /// [`Enemy::sight`] is how many tiles away an [`Enemy`] notices the player from.
+    pub sight: f32,
+    /// [`Enemy::drops`] is every [`LootDrop`] an [`Enemy`] may drop when defeated.
+    pub drops: Vec<LootDrop>,
+    /// [`Enemy::recovery`] is how an [`Enemy`] is recovering from the last hit.
+    pub recovery: HitRecovery,
+    home: (f32, f32),
+    attack_timer: f32,
+    remains_timer: f32,
+    random: Random,
+}
+
+impl Enemy {
+    /// [`Enemy::new`] creates a new [`Enemy`] at an (`x`, `y`) coordinate, which is
+    /// also where it goes back to when it loses sight of the player.
+    pub fn new(id: &str, name: &str, x: f32, y: f32, max_health: u32, damage: u32) -> Self {
+        Self {
+            id: id.to_owned(),
+            name: name.to_owned(),
+            x,
+            y,
+            speed: DEFAULT_ENEMY_SPEED,
+            sprite_id: "slime".to_owned(),
+            health: max_health,
+            max_health,
+            damage,
+            armor_rating: 0,
+            xp: 0,
+            sight: DEFAULT_SIGHT,
+            drops: Vec::new(),
+            recovery: HitRecovery::default(),
+            home: (x, y),
+            attack_timer: 0.,
+            remains_timer: REMAINS_TIME,
+            random: Random::from_id(id),
+        }
+    }
+
+    /// [`Enemy::update`] will be called every frame to move an [`Enemy`] through a
+    /// [`Map`] towards the player, whose (`x`, `y`, `width`, `height`) area is
+    /// `player`. It returns `true` if the [`Enemy`] attacks the player this frame.
+    pub fn update(&mut self, map: &Map, player: (f32, f32, f32, f32), frame_time: f32) -> bool {
+        if self.is_dead() {
+            self.remains_timer -= frame_time;
+            return false;
+        }
+        self.attack_timer = (self.attack_timer - frame_time).max(0.);
+        let knockback = self.recovery.update(frame_time);
+        (self.x, self.y) = map.move_area(self.x, self.y, (ENEMY_WIDTH, ENEMY_HEIGHT), knockback);
+        // an enemy being knocked back can't fight back
+        if self.recovery.knockback != (0., 0.) {
+            return false;
+        }
+
+        if combat::get_gap(self.get_bounds(), player) <= ENEMY_REACH {
+            if self.attack_timer > 0. {
+                return false;
+            }
+            self.attack_timer = ENEMY_ATTACK_COOLDOWN;
+            return true;
+        }
+        let (center_x, center_y) = self.get_center();
+        let player_center = (player.0 + player.2 / 2., player.1 + player.3 / 2.);
+        if (player_center.0 - center_x).hypot(player_center.1 - center_y) <= self.sight {
+            let target = (
+                player_center.0 - ENEMY_WIDTH / 2.,
+                player_center.1 - ENEMY_HEIGHT / 2.,
+            );
+            self.walk_towards(target, map, frame_time);
+        } else {
+            self.walk_towards(self.home, map, frame_time);
+        }
+        false
+    }
+
+    /// [`Enemy::draw_enemy`] will draw an [`Enemy`] where a [`Camera`] sees them using
+    /// a [`Render`] implementation, with its health above it once it has been hurt.
+    pub fn draw_enemy<R: Render>(&self, camera: &Camera, renderer: &mut R) {
+        if !camera.is_visible(self.x, self.y, ENEMY_WIDTH, ENEMY_HEIGHT) {
+            return;
+        }
+        let (x, y) = camera.world_to_screen(self.x, self.y);
+        if self.is_dead() {
+            renderer.draw_sprite(x, y, "remains");
+            return;
+        }
+        if !self.recovery.is_hidden() {
+            renderer.draw_sprite(x, y, &self.sprite_id);
+        }
+        if self.health < self.max_health {
+            let tile_size = renderer.get_tile_size();
+            renderer.draw_text(
+                x * tile_size,
+                panel::text_y(y - 1., tile_size),
+                &format!("{}/{}", self.health, self.max_health),
+                Color::Red,
+                Color::Black,
+            );
+        }
+    }
+
+    /// [`Enemy::take_hit`] will hurt an [`Enemy`] by a hit with `damage` that came
+    /// `from` an (`x`, `y`) coordinate, less what its armor stops, and knock it back.
+    /// It returns the damage taken, which is `0` while it is invulnerable.
+    pub fn take_hit(&mut self, damage: u32, from: (f32, f32)) -> u32 {
+        if self.is_dead() || self.recovery.is_invulnerable() {
+            return 0;
+        }
+        let taken = combat::get_damage_taken(damage, self.armor_rating);
+        self.health = self.health.saturating_sub(taken);
+        self.recovery.hit(from, self.get_center());
+        taken
+    }
+
+    /// [`Enemy::is_dead`] returns `true` once an [`Enemy`] has no health left.
+    pub fn is_dead(&self) -> bool {
+        self.health == 0
+    }
+
+    /// [`Enemy::is_gone`] returns `true` once the remains of a dead [`Enemy`] have
+    /// been in the world long enough to be removed.
+    pub fn is_gone(&self) -> bool {
+        self.is_dead() && self.remains_timer <= 0.
+    }
+
+    /// [`Enemy::roll_loot`] returns the id and quantity of every item an [`Enemy`]
+    /// drops, each by the chance of its [`LootDrop`].
+    pub fn roll_loot(&mut self) -> Vec<(String, u32)> {
+        let mut loot = Vec::new();
+        for drop in &self.drops {
+            if self.random.next_f32() < drop.chance {
+                loot.push((drop.item_id.clone(), drop.quantity));
+            }
+        }
+        loot
+    }
+
+    /// [`Enemy::get_center`] returns the (`x`, `y`) coordinate of the middle of an
+    /// [`Enemy`].
+    pub fn get_center(&self) -> (f32, f32) {
+        (self.x + ENEMY_WIDTH / 2., self.y + ENEMY_HEIGHT / 2.)
+    }
+
+    /// [`Enemy::get_bounds`] returns the (`x`, `y`, `width`, `height`) area an
+    /// [`Enemy`] takes up.
+    pub fn get_bounds(&self) -> (f32, f32, f32, f32) {
+        (self.x, self.y, ENEMY_WIDTH, ENEMY_HEIGHT)
+    }
+
+    /// [`Enemy::get_bottom`] returns the `y` coordinate of the feet of an [`Enemy`],
+    /// which decides what they are drawn in front of.
+    pub fn get_bottom(&self) -> f32 {
+        self.y + ENEMY_HEIGHT
+    }
+
+    // step towards a target, sliding along anything in the way
+    fn walk_towards(&mut self, target: (f32, f32), map: &Map, frame_time: f32) {
+        let (difference_x, difference_y) = (target.0 - self.x, target.1 - self.y);
+        let distance = difference_x.hypot(difference_y);
+        if distance <= ARRIVAL_DISTANCE {
+            return;
+        }
+        let step = (self.speed * frame_time).min(distance);
+        let delta = (
+            difference_x / distance * step,
+            difference_y / distance * step,
+        );
+        (self.x, self.y) = map.move_area(self.x, self.y, (ENEMY_WIDTH, ENEMY_HEIGHT), delta);
+    }
+}
+
+/// [`load_enemies`] reads and parses the enemy data file at `path`, checking that
+/// every [`Enemy`] starts somewhere it can stand on a [`Map`] and only drops items
+/// in an [`ItemCatalog`].
+pub fn load_enemies(path: &str, map: &Map, items: &ItemCatalog) -> Result<Vec<Enemy>, EnemyError> {
+    let records = record::read_records(path)?;
+    parse_enemies(&records, map, items)
+}
+
+fn parse_enemies(
+    records: &[Record],
+    map: &Map,
+    items: &ItemCatalog,
+) -> Result<Vec<Enemy>, EnemyError> {
+    let mut enemies: Vec<Enemy> = Vec::new();
+    for enemy_record in records.iter().filter(|r| r.kind == "enemy") {
+        let line = enemy_record.line;
+        if enemies.iter().any(|e| e.id == enemy_record.id) {
+            return Err(EnemyError::DuplicateId {
+                id: enemy_record.id.clone(),
+                line,
+            });
+        }
+        let mut enemy = Enemy::new(
+            &enemy_record.id,
+            enemy_record.require("name")?,
+            enemy_record.parse_required("x")?,
+            enemy_record.parse_required("y")?,
+            enemy_record.parse_required::<u32>("health")?.max(1),
+            enemy_record.parse_required("damage")?,
+        );
+        enemy.speed = enemy_record.parse("speed", DEFAULT_ENEMY_SPEED)?;
+        enemy.armor_rating = enemy_record.parse("armor_rating", 0)?;
+        enemy.xp = enemy_record.parse("xp", 0)?;
+        enemy.sight = enemy_record.parse("sight", DEFAULT_SIGHT)?;
+        enemy.sprite_id = enemy_record.require("sprite")?.to_owned();
+        if !sprite::is_known_sprite(&enemy.sprite_id) {
+            return Err(EnemyError::UnknownSpriteId {
+                id: enemy.id,
+                sprite_id: enemy.sprite_id,
+                line,
+            });
+        }
+        for (value, drop_line) in enemy_record.get_all("drop") {
+            let drop: LootDrop = record::parse_value(value, "drop", drop_line)?;
+            if items.get(&drop.item_id).is_none() {
+                return Err(EnemyError::UnknownItem {
+                    id: enemy.id,
+                    item_id: drop.item_id,
+                    line: drop_line,
+                });
+            }
+            enemy.drops.push(drop);
+        }
+        if !map.is_area_passable(enemy.x, enemy.y, ENEMY_WIDTH, ENEMY_HEIGHT) {
+            return Err(EnemyError::Blocked { id: enemy.id, line });
+        }
+        enemies.push(enemy);
+    }
+    Ok(enemies)
+}
+
+/// [`EnemyError`] represents a problem loading every [`Enemy`].
+#[derive(Debug)]
+pub enum EnemyError {
+    /// [`EnemyError::Data`] means the enemy data file couldn't be read or parsed.
+    Data(DataError),
+    /// [`EnemyError::DuplicateId`] means two [`Enemy`] were defined with the same `id`.
+    DuplicateId { id: String, line: usize },
+    /// [`EnemyError::UnknownSpriteId`] means an [`Enemy`] uses a sprite that doesn't
+    /// exist.
+    UnknownSpriteId {
+        id: String,
+        sprite_id: String,
+        line: usize,
+    },
+    /// [`EnemyError::UnknownItem`] means an [`Enemy`] drops an item that isn't defined.
+    UnknownItem {
+        id: String,
+        item_id: String,
+        line: usize,
+    },
+    /// [`EnemyError::Blocked`] means an [`Enemy`] starts inside a wall or building.
+    Blocked { id: String, line: usize },
+}
+
+impl From<DataError> for EnemyError {
+    fn from(error: DataError) -> Self {
+        EnemyError::Data(error)
+    }
+}
+
+impl fmt::Display for EnemyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnemyError::Data(error) => write!(f, "enemy data error: {error}"),
+            EnemyError::DuplicateId { id, line } => {
+                write!(f, "line {line}: enemy `{id}` is defined more than once")
+            }
+            EnemyError::UnknownSpriteId {
+                id,
+                sprite_id,
+                line,
+            } => write!(
+                f,
+                "line {line}: enemy `{id}` uses unknown sprite `{sprite_id}`"
+            ),
+            EnemyError::UnknownItem { id, item_id, line } => {
+                write!(
+                    f,
+                    "line {line}: enemy `{id}` drops unknown item `{item_id}`"
+                )
+            }
+            EnemyError::Blocked { id, line } => {
+                write!(
+                    f,
+                    "line {line}: enemy `{id}` starts somewhere it can't stand"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for EnemyError {}
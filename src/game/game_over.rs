@@ -0,0 +1,149 @@
+use crate::data::record::{self, DataError};
+
+/// [`RESPAWN_PATH`] is the data file that configures what dying costs the player.
+pub const RESPAWN_PATH: &str = "assets/respawn.txt";
+
+/// [`RespawnPenalty`] decides what the player loses when they respawn after dying.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RespawnPenalty {
+    /// [`RespawnPenalty::money_lost`] is the fraction of their money the player
+    /// loses, from `0.` to `1.`.
+    pub money_lost: f32,
+    /// [`RespawnPenalty::drop_items`] is `true` if the player leaves their inventory
+    /// where they fell, to be picked up again.
+    pub drop_items: bool,
+}
+
+impl RespawnPenalty {
+    /// [`RespawnPenalty::load`] reads the first `[respawn id]` record of the data file
+    /// at `path`, where any missing field keeps its default.
+    pub fn load(path: &str) -> Result<Self, DataError> {
+        let records = record::read_records(path)?;
+        let Some(penalty_record) = records.iter().find(|r| r.kind == "respawn") else {
+            return Ok(RespawnPenalty::default());
+        };
+        let default = RespawnPenalty::default();
+        let penalty = RespawnPenalty {
+            money_lost: penalty_record.parse("money_lost", default.money_lost)?,
+            drop_items: penalty_record.parse("drop_items", default.drop_items)?,
+        };
+        if !(0. ..=1.).contains(&penalty.money_lost) {
+            return Err(DataError::InvalidField {
+                line: penalty_record.get_line("money_lost"),
+                field: "money_lost".to_owned(),
+                value: penalty.money_lost.to_string(),
+            });
+        }
+        Ok(penalty)
+    }
+}
+
+impl Default for RespawnPenalty {
+    fn default() -> Self {
+        Self {
+            money_lost: 0.25,
+            drop_items: false,
+        }
+    }
+}
+
+/// [`GameOverOption`] is an entry of the game over screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameOverOption {
+    /// [`GameOverOption::LoadSave`] goes back to the last save.
+    LoadSave,
+    /// [`GameOverOption::Respawn`] carries on from the spawn point, paying the
+    /// [`RespawnPenalty`].
+    Respawn,
+    /// [`GameOverOption::QuitToTitle`] gives up and goes back to the title screen.
+    QuitToTitle,
+}
+
+impl GameOverOption {
+    /// [`GameOverOption::ALL`] is every [`GameOverOption`] in the order they are shown.
+    pub const ALL: [GameOverOption; 3] = [
+        GameOverOption::LoadSave,
+        GameOverOption::Respawn,
+        GameOverOption::QuitToTitle,
+    ];
+}
+
+impl std::fmt::Display for GameOverOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameOverOption::LoadSave => write!(f, "Load Last Save"),
+            GameOverOption::Respawn => write!(f, "Respawn"),
+            GameOverOption::QuitToTitle => write!(f, "Quit to Title"),
+        }
+    }
+}
+
+/// [`GameOverMenu`] holds the state of the game over screen while it is open.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GameOverMenu {
+    /// [`GameOverMenu::selected`] is the index of the selected [`GameOverOption`].
+    pub selected: usize,
+}
+
+impl GameOverMenu {
+    /// [`GameOverMenu::move_cursor`] moves the selected option by `y` rows, staying
+    /// within [`GameOverOption::ALL`].
+    pub fn move_cursor(&mut self, y: i32) {
+        let last = GameOverOption::ALL.len() as i32 - 1;
+        self.selected = (self.selected as i32 + y).clamp(0, last) as usize;
+    }
+
+    /// [`GameOverMenu::get_selected`] returns the selected [`GameOverOption`].
+    pub fn get_selected(&self) -> GameOverOption {
+        GameOverOption::ALL[self.selected.min(GameOverOption::ALL.len() - 1)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // write `source` to a respawn data file and load it
+    fn load_penalty(file_name: &str, source: &str) -> Result<RespawnPenalty, DataError> {
+        let path = std::env::temp_dir().join(file_name);
+        let path = path.to_str().unwrap();
+        std::fs::write(path, source).unwrap();
+        let penalty = RespawnPenalty::load(path);
+        std::fs::remove_file(path).unwrap();
+        penalty
+    }
+
+    #[test]
+    fn missing_fields_keep_their_defaults() {
+        let penalty = load_penalty(
+            "rust_and_ruin_default_respawn.txt",
+            "[respawn player]\ndrop_items = true\n",
+        );
+        assert_eq!(
+            penalty.unwrap(),
+            RespawnPenalty {
+                drop_items: true,
+                ..Default::default()
+            }
+        );
+        let penalty = load_penalty("rust_and_ruin_empty_respawn.txt", "");
+        assert_eq!(penalty.unwrap(), RespawnPenalty::default());
+    }
+
+    #[test]
+    fn money_lost_out_of_range_is_reported_on_its_line() {
+        let penalty = load_penalty(
+            "rust_and_ruin_invalid_respawn.txt",
+            "[respawn player]\ndrop_items = true\nmoney_lost = 1.5\n",
+        );
+        match penalty {
+            Err(DataError::InvalidField { line, field, value }) => {
+                assert_eq!(
+                    (line, field.as_str(), value.as_str()),
+                    (3, "money_lost", "1.5")
+                )
+            }
+            other => panic!("expected an invalid field, got {other:?}"),
+        }
+    }
+}
@@ -1,26 +1,115 @@
-use super::player::Player;
+use super::state::GameState;
 use crate::{
     events::{self, event_listener::EventListener},
-    maps::map::Map,
-    renderer::render::Render,
+    renderer::{
+        effect::{EffectId, EffectParams},
+        render::Render,
+    },
     ui::{self, interface::Interface},
 };
 
 /// [`Mode`] represents the current mode of the game.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Mode {
     TitleScreen,
     Playing,
     Inventory,
     Crafting,
+    Dialog,
+    GameOver,
+    Paused,
+    Settings,
 }
 
 impl Interface for Mode {
-    fn draw_interface<R: Render>(&self, map: &Map, player: &Player, renderer: &mut R) {
+    fn draw_interface<R: Render>(&self, state: &GameState, renderer: &mut R) {
         match self {
-            Mode::TitleScreen => ui::main_menu::draw_title_screen(renderer),
-            Mode::Playing => ui::playing::draw_playing_screen(map, player, renderer),
-            Mode::Crafting => ui::crafting::draw_crafting_screen(map, renderer),
-            Mode::Inventory => todo!(),
+            Mode::TitleScreen => ui::main_menu::draw_title_screen(
+                &state.title_menu,
+                state.has_save,
+                state.message.as_deref(),
+                &state.get_launch_settings().input_map,
+                renderer,
+            ),
+            Mode::Playing => {
+                state.draw_world_layer(renderer);
+                ui::playing::draw_playing_screen(
+                    &state.player,
+                    &state.camera,
+                    state.player.get_interactable(&state.map, &state.npcs),
+                    &state.xp_curve,
+                    state.notification.as_deref(),
+                    &state.get_launch_settings().input_map,
+                    renderer,
+                )
+            }
+            Mode::Crafting => {
+                state.draw_world_layer(renderer);
+                ui::crafting::draw_crafting_screen(
+                    &state.player,
+                    &state.recipes,
+                    &state.items,
+                    &state.crafting,
+                    &state.get_launch_settings().input_map,
+                    renderer,
+                )
+            }
+            Mode::Inventory => {
+                state.draw_world_layer(renderer);
+                ui::inventory::draw_inventory_screen(
+                    &state.player,
+                    state.notification.as_deref(),
+                    &state.get_launch_settings().input_map,
+                    renderer,
+                )
+            }
+            Mode::Dialog => {
+                state.draw_world_layer(renderer);
+                ui::dialog::draw_dialog_screen(
+                    &state.player,
+                    &state.dialogs,
+                    &state.conversation,
+                    &state.flags,
+                    state.notification.as_deref(),
+                    &state.get_launch_settings().input_map,
+                    renderer,
+                )
+            }
+            Mode::GameOver => {
+                state.draw_world_layer(renderer);
+                ui::game_over::draw_game_over_screen(
+                    &state.game_over,
+                    state.has_save,
+                    &state.respawn_penalty,
+                    state.notification.as_deref(),
+                    &state.get_launch_settings().input_map,
+                    renderer,
+                )
+            }
+            Mode::Paused => {
+                state.draw_world_layer(renderer);
+                ui::pause::draw_pause_screen(
+                    &state.pause,
+                    state.notification.as_deref(),
+                    &state.get_launch_settings().input_map,
+                    renderer,
+                )
+            }
+            Mode::Settings => {
+                // the settings screen stays on top of wherever it was opened from
+                if state.settings_menu.return_mode == Mode::TitleScreen {
+                    renderer.draw_background_effect(EffectId::Starfield, EffectParams::default());
+                } else {
+                    state.draw_world_layer(renderer);
+                    renderer.draw_background_effect(EffectId::Dim, EffectParams::default());
+                }
+                ui::settings::draw_settings_screen(
+                    &state.settings_menu,
+                    state.get_launch_settings(),
+                    state.notification.as_deref(),
+                    renderer,
+                )
+            }
         }
     }
 }
@@ -31,7 +120,45 @@ impl EventListener for Mode {
             Mode::TitleScreen => events::title_screen::handle_title_screen_input(input),
             Mode::Playing => events::playing::handle_playing_input(input),
             Mode::Crafting => events::crafting::handle_crafting_input(input),
-            Mode::Inventory => todo!(),
+            Mode::Inventory => events::inventory::handle_inventory_input(input),
+            Mode::Dialog => events::dialog::handle_dialog_input(input),
+            Mode::GameOver => events::game_over::handle_game_over_input(input),
+            Mode::Paused => events::paused::handle_paused_input(input),
+            Mode::Settings => events::settings::handle_settings_input(input),
+        }
+    }
+}
+
+impl Mode {
+    /// [`Mode::get_id`] gets a [`&str`](str) that identifies a [`Mode`] in save files.
+    pub fn get_id(&self) -> &str {
+        match self {
+            Mode::TitleScreen => "title_screen",
+            Mode::Playing => "playing",
+            Mode::Inventory => "inventory",
+            Mode::Crafting => "crafting",
+            Mode::Dialog => "dialog",
+            Mode::GameOver => "game_over",
+            Mode::Paused => "paused",
+            Mode::Settings => "settings",
+        }
+    }
+}
+
+impl std::str::FromStr for Mode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "title_screen" => Ok(Mode::TitleScreen),
+            "playing" => Ok(Mode::Playing),
+            "inventory" => Ok(Mode::Inventory),
+            "crafting" => Ok(Mode::Crafting),
+            "dialog" => Ok(Mode::Dialog),
+            "game_over" => Ok(Mode::GameOver),
+            "paused" => Ok(Mode::Paused),
+            "settings" => Ok(Mode::Settings),
+            _ => Err(()),
         }
     }
 }
@@ -43,6 +170,10 @@ impl std::fmt::Display for Mode {
             Mode::Playing => write!(f, "Playing"),
             Mode::Inventory => write!(f, "Inventory"),
             Mode::Crafting => write!(f, "Crafting"),
+            Mode::Dialog => write!(f, "Dialog"),
+            Mode::GameOver => write!(f, "Game Over"),
+            Mode::Paused => write!(f, "Paused"),
+            Mode::Settings => write!(f, "Settings"),
         }
     }
 }
@@ -0,0 +1,156 @@
+use super::player::Player;
+use crate::maps::map::Map;
+
+// how many times its top speed a player gains or loses every second
+const ACCELERATION: f32 = 10.;
+const FRICTION: f32 = 12.;
+
+/// [`MovementController`] turns the directions held during a frame into smooth
+/// [`Player`] movement, with the same top speed in every direction.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MovementController {
+    /// [`MovementController::direction`] is the sum of every direction held this
+    /// frame, where each axis is from `-1.` to `1.`, and less than a full tilt of a
+    /// stick moves slower.
+    pub direction: (f32, f32),
+    /// [`MovementController::velocity`] is how many tiles per second a [`Player`]
+    /// is moving on each axis.
+    pub velocity: (f32, f32),
+    /// [`MovementController::hold_time`] is how many seconds a direction stays held
+    /// after it was last pushed, for backends that only report key presses and never
+    /// hold a key down. It is `0.` for backends that send held keys every frame.
+    pub hold_time: f32,
+    held_direction: (f32, f32),
+    hold_timer: f32,
+}
+
+impl MovementController {
+    /// [`MovementController::push_direction`] adds a held direction for this frame.
+    /// Opposite directions cancel out, and repeats of a direction don't add up.
+    pub fn push_direction(&mut self, x: f32, y: f32) {
+        self.direction = (
+            (self.direction.0 + x).clamp(-1., 1.),
+            (self.direction.1 + y).clamp(-1., 1.),
+        );
+    }
+
+    /// [`MovementController::update`] will be called every frame to speed a
+    /// [`Player`] up towards the held direction, or slow them down when nothing is
+    /// held, then move them through a [`Map`] and clear the held direction.
+    pub fn update(&mut self, player: &mut Player, map: &Map, frame_time: f32) {
+        let mut direction = limit_length(std::mem::take(&mut self.direction));
+        if direction != (0., 0.) {
+            self.held_direction = direction;
+            self.hold_timer = self.hold_time;
+        } else if self.hold_timer > 0. {
+            self.hold_timer -= frame_time;
+            direction = self.held_direction;
+        }
+        let target = (direction.0 * player.speed, direction.1 * player.speed);
+        let rate = if direction == (0., 0.) {
+            FRICTION
+        } else {
+            ACCELERATION
+        };
+        self.velocity = approach(self.velocity, target, rate * player.speed * frame_time);
+
+        if direction != (0., 0.) {
+            player.face(direction);
+        }
+        let (start_x, start_y) = (player.x, player.y);
+        player.move_player(
+            (self.velocity.0 * frame_time, self.velocity.1 * frame_time),
+            map,
+        );
+        // stop pushing into whatever the player bumped into
+        if player.x == start_x {
+            self.velocity.0 = 0.;
+        }
+        if player.y == start_y {
+            self.velocity.1 = 0.;
+        }
+    }
+
+    /// [`MovementController::stop`] brings a [`Player`] to an immediate stop, i.e.:
+    /// when a menu opens.
+    pub fn stop(&mut self) {
+        *self = Self {
+            hold_time: self.hold_time,
+            ..Default::default()
+        };
+    }
+}
+
+// shorten a vector to a length of at most 1, so diagonals are no faster than
+// straight lines, while a partly tilted stick still moves slower
+fn limit_length((x, y): (f32, f32)) -> (f32, f32) {
+    let length = x.hypot(y);
+    if length <= 1. {
+        (x, y)
+    } else {
+        (x / length, y / length)
+    }
+}
+
+// move `from` towards `to` by at most `max_change`
+fn approach(from: (f32, f32), to: (f32, f32), max_change: f32) -> (f32, f32) {
+    let (difference_x, difference_y) = (to.0 - from.0, to.1 - from.1);
+    let distance = difference_x.hypot(difference_y);
+    if distance <= max_change {
+        to
+    } else {
+        (
+            from.0 + difference_x / distance * max_change,
+            from.1 + difference_y / distance * max_change,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME_TIME: f32 = 1. / 30.;
+
+    // push one direction for a single frame, then let the player coast for a second
+    fn tap_right(movement: &mut MovementController) -> f32 {
+        let map = Map::new(20, 20);
+        let mut player = Player::new(5., 5.);
+        player.speed = 4.;
+        movement.push_direction(1., 0.);
+        for _ in 0..30 {
+            movement.update(&mut player, &map, FRAME_TIME);
+        }
+        player.x - 5.
+    }
+
+    #[test]
+    fn a_single_press_barely_moves_without_hold_time() {
+        let distance = tap_right(&mut MovementController::default());
+        assert!(distance > 0. && distance < 0.1);
+    }
+
+    #[test]
+    fn a_single_press_keeps_moving_for_the_hold_time() {
+        let mut movement = MovementController {
+            hold_time: 0.25,
+            ..Default::default()
+        };
+        let distance = tap_right(&mut movement);
+        // up to speed for most of a quarter second, then sliding to a stop
+        assert!(distance > 0.75, "only moved {distance}");
+        assert_eq!(movement.velocity, (0., 0.));
+    }
+
+    #[test]
+    fn stop_keeps_the_hold_time() {
+        let mut movement = MovementController {
+            hold_time: 0.25,
+            ..Default::default()
+        };
+        movement.push_direction(1., 0.);
+        movement.stop();
+        assert_eq!(movement.hold_time, 0.25);
+        assert_eq!(movement.direction, (0., 0.));
+    }
+}
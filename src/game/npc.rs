@@ -0,0 +1,312 @@
+use super::{dialog::DialogBook, random::Random};
+use crate::{
+    data::record::{self, DataError, Record},
+    maps::{camera::Camera, map::Map},
+    renderer::{render::Render, sprite},
+};
+use std::{fmt, str::FromStr};
+
+/// [`NPCS_PATH`] is the data file every [`Npc`] is defined in.
+pub const NPCS_PATH: &str = "assets/npcs.txt";
+
+const NPC_WIDTH: f32 = sprite::CHARACTER_SIZE.0;
+const NPC_HEIGHT: f32 = sprite::CHARACTER_SIZE.1;
+const DEFAULT_NPC_SPEED: f32 = 1.5;
+// how close an npc has to get to where it is going to count as there
+const ARRIVAL_DISTANCE: f32 = 0.1;
+const MIN_WANDER_WAIT: f32 = 1.;
+const MAX_WANDER_WAIT: f32 = 3.;
+
+/// [`Behavior`] decides how an [`Npc`] moves around the world.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Behavior {
+    /// [`Behavior::Idle`] stands still.
+    Idle,
+    /// [`Behavior::Wander`] walks to random places within `radius` tiles of where
+    /// the [`Npc`] started, waiting a moment at each one.
+    Wander { radius: f32 },
+    /// [`Behavior::Patrol`] walks between `waypoints` in order, then starts over.
+    Patrol { waypoints: Vec<(f32, f32)> },
+    /// [`Behavior::Follow`] walks towards the player until within `distance` tiles.
+    Follow { distance: f32 },
+}
+
+impl FromStr for Behavior {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split_whitespace();
+        let kind = fields.next().ok_or(())?;
+        let numbers = fields
+            .map(|f| f.parse::<f32>().map_err(|_| ()))
+            .collect::<Result<Vec<f32>, ()>>()?;
+        match (kind, numbers.as_slice()) {
+            ("idle", []) => Ok(Behavior::Idle),
+            ("wander", [radius]) if *radius >= 0. => Ok(Behavior::Wander { radius: *radius }),
+            ("follow", [distance]) if *distance >= 0. => Ok(Behavior::Follow {
+                distance: *distance,
+            }),
+            ("patrol", points) if !points.is_empty() && points.len().is_multiple_of(2) => {
+                Ok(Behavior::Patrol {
+                    waypoints: points.chunks(2).map(|p| (p[0], p[1])).collect(),
+                })
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+/// [`Npc`] is a character in the world that isn't controlled by the player.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Npc {
+    /// [`Npc::id`] is the stable identifier of an [`Npc`].
+    pub id: String,
+    /// [`Npc::name`] is the display name of an [`Npc`].
+    pub name: String,
+    /// [`Npc::x`] is the horizontal world location of an [`Npc`].
+    pub x: f32,
+    /// [`Npc::y`] is the vertical world location of an [`Npc`].
+    pub y: f32,
+    /// [`Npc::speed`] is how many tiles per second an [`Npc`] walks.
+    pub speed: f32,
+    /// [`Npc::sprite_id`] is the id of the sprite an [`Npc`] is drawn with.
+    pub sprite_id: String,
+    /// [`Npc::behavior`] is the [`Behavior`] an [`Npc`] follows.
+    pub behavior: Behavior,
+    /// [`Npc::dialog`] is [`Some`] id of the [`Dialog`](super::dialog::Dialog) had by
+    /// talking to an [`Npc`], and [`None`] if they have nothing to say.
+    pub dialog: Option<String>,
+    home: (f32, f32),
+    target: Option<(f32, f32)>,
+    next_waypoint: usize,
+    wait_timer: f32,
+    random: Random,
+}
+
+impl Npc {
+    /// [`Npc::new`] creates a new [`Npc`] at an (`x`, `y`) coordinate, which is also
+    /// where a [`Behavior::Wander`] wanders around.
+    pub fn new(id: &str, name: &str, x: f32, y: f32, behavior: Behavior) -> Self {
+        Self {
+            id: id.to_owned(),
+            name: name.to_owned(),
+            x,
+            y,
+            speed: DEFAULT_NPC_SPEED,
+            sprite_id: "villager".to_owned(),
+            behavior,
+            dialog: None,
+            home: (x, y),
+            target: None,
+            next_waypoint: 0,
+            wait_timer: 0.,
+            // every npc wanders differently, but the same way every time
+            random: Random::from_id(id),
+        }
+    }
+
+    /// [`Npc::update`] will be called every frame to move an [`Npc`] through a
+    /// [`Map`] by its [`Behavior`], where `player` is the (`x`, `y`) coordinate of
+    /// the middle of the player.
+    pub fn update(&mut self, map: &Map, player: (f32, f32), frame_time: f32) {
+        match self.behavior.clone() {
+            Behavior::Idle => {}
+            Behavior::Wander { radius } => {
+                if self.wait_timer > 0. {
+                    self.wait_timer -= frame_time;
+                    return;
+                }
+                let target = match self.target {
+                    Some(target) => target,
+                    None => {
+                        let angle = self.random.next_f32() * std::f32::consts::TAU;
+                        let distance = self.random.next_f32() * radius;
+                        let target = (
+                            self.home.0 + angle.cos() * distance,
+                            self.home.1 + angle.sin() * distance,
+                        );
+                        self.target = Some(target);
+                        target
+                    }
+                };
+                if self.walk_towards(target, map, frame_time) {
+                    self.target = None;
+                    self.wait_timer = MIN_WANDER_WAIT
+                        + self.random.next_f32() * (MAX_WANDER_WAIT - MIN_WANDER_WAIT);
+                }
+            }
+            Behavior::Patrol { waypoints } => {
+                let target = waypoints[self.next_waypoint % waypoints.len()];
+                if self.walk_towards(target, map, frame_time) {
+                    self.next_waypoint = (self.next_waypoint + 1) % waypoints.len();
+                }
+            }
+            Behavior::Follow { distance } => {
+                let (center_x, center_y) = self.get_center();
+                if (player.0 - center_x).hypot(player.1 - center_y) > distance {
+                    let target = (player.0 - NPC_WIDTH / 2., player.1 - NPC_HEIGHT / 2.);
+                    self.walk_towards(target, map, frame_time);
+                }
+            }
+        }
+    }
+
+    /// [`Npc::draw_npc`] will draw an [`Npc`] where a [`Camera`] sees them using a
+    /// [`Render`] implementation.
+    pub fn draw_npc<R: Render>(&self, camera: &Camera, renderer: &mut R) {
+        if camera.is_visible(self.x, self.y, NPC_WIDTH, NPC_HEIGHT) {
+            let (x, y) = camera.world_to_screen(self.x, self.y);
+            renderer.draw_sprite(x, y, &self.sprite_id);
+        }
+    }
+
+    /// [`Npc::get_center`] returns the (`x`, `y`) coordinate of the middle of an
+    /// [`Npc`].
+    pub fn get_center(&self) -> (f32, f32) {
+        (self.x + NPC_WIDTH / 2., self.y + NPC_HEIGHT / 2.)
+    }
+
+    /// [`Npc::get_size`] returns the width and height in tiles of an [`Npc`].
+    pub fn get_size(&self) -> (f32, f32) {
+        (NPC_WIDTH, NPC_HEIGHT)
+    }
+
+    /// [`Npc::get_bottom`] returns the `y` coordinate of the feet of an [`Npc`],
+    /// which decides what they are drawn in front of.
+    pub fn get_bottom(&self) -> f32 {
+        self.y + NPC_HEIGHT
+    }
+
+    // step towards a target, and return `true` once there or if blocked on the way
+    fn walk_towards(&mut self, target: (f32, f32), map: &Map, frame_time: f32) -> bool {
+        let (difference_x, difference_y) = (target.0 - self.x, target.1 - self.y);
+        let distance = difference_x.hypot(difference_y);
+        if distance <= ARRIVAL_DISTANCE {
+            return true;
+        }
+        let step = (self.speed * frame_time).min(distance);
+        let delta = (
+            difference_x / distance * step,
+            difference_y / distance * step,
+        );
+        let (x, y) = map.move_area(self.x, self.y, (NPC_WIDTH, NPC_HEIGHT), delta);
+        let is_blocked = step > 0. && x == self.x && y == self.y;
+        (self.x, self.y) = (x, y);
+        is_blocked
+    }
+}
+
+/// [`load_npcs`] reads and parses the npc data file at `path`, checking that every
+/// [`Npc`] starts somewhere it can stand on a [`Map`] and has a dialog in a
+/// [`DialogBook`].
+pub fn load_npcs(path: &str, map: &Map, dialogs: &DialogBook) -> Result<Vec<Npc>, NpcError> {
+    let records = record::read_records(path)?;
+    parse_npcs(&records, map, dialogs)
+}
+
+fn parse_npcs(records: &[Record], map: &Map, dialogs: &DialogBook) -> Result<Vec<Npc>, NpcError> {
+    let mut npcs: Vec<Npc> = Vec::new();
+    for npc_record in records.iter().filter(|r| r.kind == "npc") {
+        if npcs.iter().any(|n| n.id == npc_record.id) {
+            return Err(NpcError::DuplicateId {
+                id: npc_record.id.clone(),
+                line: npc_record.line,
+            });
+        }
+        let mut npc = Npc::new(
+            &npc_record.id,
+            npc_record.require("name")?,
+            npc_record.parse_required("x")?,
+            npc_record.parse_required("y")?,
+            npc_record.parse("behavior", Behavior::Idle)?,
+        );
+        npc.speed = npc_record.parse("speed", DEFAULT_NPC_SPEED)?;
+        npc.sprite_id = npc_record.require("sprite")?.to_owned();
+        if !sprite::is_known_sprite(&npc.sprite_id) {
+            return Err(NpcError::UnknownSpriteId {
+                id: npc.id,
+                sprite_id: npc.sprite_id,
+                line: npc_record.line,
+            });
+        }
+        npc.dialog = npc_record.get("dialog").map(str::to_owned);
+        if let Some(dialog_id) = &npc.dialog
+            && dialogs.get(dialog_id).is_none()
+        {
+            return Err(NpcError::UnknownDialog {
+                id: npc.id,
+                dialog_id: dialog_id.clone(),
+                line: npc_record.line,
+            });
+        }
+        if !map.is_area_passable(npc.x, npc.y, NPC_WIDTH, NPC_HEIGHT) {
+            return Err(NpcError::Blocked {
+                id: npc.id,
+                line: npc_record.line,
+            });
+        }
+        npcs.push(npc);
+    }
+    Ok(npcs)
+}
+
+/// [`NpcError`] represents a problem loading every [`Npc`].
+#[derive(Debug)]
+pub enum NpcError {
+    /// [`NpcError::Data`] means the npc data file couldn't be read or parsed.
+    Data(DataError),
+    /// [`NpcError::DuplicateId`] means two [`Npc`] were defined with the same `id`.
+    DuplicateId { id: String, line: usize },
+    /// [`NpcError::UnknownSpriteId`] means an [`Npc`] uses a sprite that doesn't exist.
+    UnknownSpriteId {
+        id: String,
+        sprite_id: String,
+        line: usize,
+    },
+    /// [`NpcError::UnknownDialog`] means an [`Npc`] has a dialog that doesn't exist.
+    UnknownDialog {
+        id: String,
+        dialog_id: String,
+        line: usize,
+    },
+    /// [`NpcError::Blocked`] means an [`Npc`] starts inside a wall or building.
+    Blocked { id: String, line: usize },
+}
+
+impl From<DataError> for NpcError {
+    fn from(error: DataError) -> Self {
+        NpcError::Data(error)
+    }
+}
+
+impl fmt::Display for NpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NpcError::Data(error) => write!(f, "npc data error: {error}"),
+            NpcError::DuplicateId { id, line } => {
+                write!(f, "line {line}: npc `{id}` is defined more than once")
+            }
+            NpcError::UnknownSpriteId {
+                id,
+                sprite_id,
+                line,
+            } => write!(
+                f,
+                "line {line}: npc `{id}` uses unknown sprite `{sprite_id}`"
+            ),
+            NpcError::UnknownDialog {
+                id,
+                dialog_id,
+                line,
+            } => write!(
+                f,
+                "line {line}: npc `{id}` has unknown dialog `{dialog_id}`"
+            ),
+            NpcError::Blocked { id, line } => {
+                write!(f, "line {line}: npc `{id}` starts somewhere it can't stand")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NpcError {}
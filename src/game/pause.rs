@@ -0,0 +1,99 @@
+/// [`PauseOption`] is an entry of the pause menu.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PauseOption {
+    /// [`PauseOption::Resume`] closes the pause menu and carries on playing.
+    Resume,
+    /// [`PauseOption::Save`] saves the game without leaving the pause menu.
+    Save,
+    /// [`PauseOption::Settings`] opens the settings screen.
+    Settings,
+    /// [`PauseOption::QuitToTitle`] leaves the game for the title screen.
+    QuitToTitle,
+    /// [`PauseOption::QuitToDesktop`] closes the game.
+    QuitToDesktop,
+}
+
+impl PauseOption {
+    /// [`PauseOption::ALL`] is every [`PauseOption`] in the order they are shown.
+    pub const ALL: [PauseOption; 5] = [
+        PauseOption::Resume,
+        PauseOption::Save,
+        PauseOption::Settings,
+        PauseOption::QuitToTitle,
+        PauseOption::QuitToDesktop,
+    ];
+
+    /// [`PauseOption::is_quitting`] returns `true` if a [`PauseOption`] leaves the
+    /// game, losing any unsaved progress.
+    pub fn is_quitting(&self) -> bool {
+        matches!(self, PauseOption::QuitToTitle | PauseOption::QuitToDesktop)
+    }
+}
+
+impl std::fmt::Display for PauseOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PauseOption::Resume => write!(f, "Resume"),
+            PauseOption::Save => write!(f, "Save"),
+            PauseOption::Settings => write!(f, "Settings"),
+            PauseOption::QuitToTitle => write!(f, "Quit to Title"),
+            PauseOption::QuitToDesktop => write!(f, "Quit to Desktop"),
+        }
+    }
+}
+
+/// [`PauseMenu`] holds the state of the pause menu while it is open.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PauseMenu {
+    /// [`PauseMenu::selected`] is the index of the selected [`PauseOption`].
+    pub selected: usize,
+    /// [`PauseMenu::has_unsaved_progress`] is `true` if the game has changed since it
+    /// was last saved or loaded.
+    pub has_unsaved_progress: bool,
+    /// [`PauseMenu::is_warning`] is `true` once the player has been warned that
+    /// quitting loses their unsaved progress.
+    pub is_warning: bool,
+}
+
+impl PauseMenu {
+    /// [`PauseMenu::new`] creates a new [`PauseMenu`] with [`PauseOption::Resume`]
+    /// selected, knowing if the game `has_unsaved_progress`.
+    pub fn new(has_unsaved_progress: bool) -> Self {
+        Self {
+            has_unsaved_progress,
+            ..Default::default()
+        }
+    }
+
+    /// [`PauseMenu::move_cursor`] moves the selected option by `y` rows, staying
+    /// within [`PauseOption::ALL`].
+    pub fn move_cursor(&mut self, y: i32) {
+        let last = PauseOption::ALL.len() as i32 - 1;
+        self.select((self.selected as i32 + y).clamp(0, last) as usize);
+    }
+
+    /// [`PauseMenu::select`] selects the [`PauseOption`] at `index`. Selecting
+    /// something else takes back any warning about unsaved progress.
+    pub fn select(&mut self, index: usize) {
+        if index != self.selected && index < PauseOption::ALL.len() {
+            self.selected = index;
+            self.is_warning = false;
+        }
+    }
+
+    /// [`PauseMenu::get_selected`] returns the selected [`PauseOption`].
+    pub fn get_selected(&self) -> PauseOption {
+        PauseOption::ALL[self.selected.min(PauseOption::ALL.len() - 1)]
+    }
+
+    /// [`PauseMenu::confirm_quit`] returns `true` if the player can quit, which is
+    /// when nothing would be lost or they have already been warned. Otherwise, the
+    /// player is warned and has to choose to quit again.
+    pub fn confirm_quit(&mut self) -> bool {
+        if !self.has_unsaved_progress || self.is_warning {
+            return true;
+        }
+        self.is_warning = true;
+        false
+    }
+}
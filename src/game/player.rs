@@ -1,99 +1,329 @@
+use super::{
+    combat::{self, ATTACK_COOLDOWN, ATTACK_REACH, HitRecovery, SWING_TIME},
+    npc::Npc,
+    stats::{LevelUp, Stats, XpCurve},
+};
 use crate::{
-    maps::{map::Map, tile::Tile},
-    renderer::{
-        color::Color,
-        render::{Render, TILE_SIZE},
+    items::{
+        currency::Wallet,
+        equipment::{EquipError, EquipSlot, Equipment},
+        inventory::Inventory,
     },
+    maps::{building::Building, camera::Camera, map::Map},
+    renderer::{color::Color, render::Render, sprite},
 };
 
-const BASE_MOVEMENT_SPEED: f32 = 4.;
-const PLAYER_WIDTH: f32 = 51.;
-const PLAYER_HEIGHT: f32 = 93.;
-const RELATIVE_PLAYER_WIDTH: f32 = PLAYER_WIDTH / TILE_SIZE;
-const RELATIVE_PLAYER_HEIGHT: f32 = PLAYER_HEIGHT / TILE_SIZE;
+const RELATIVE_PLAYER_WIDTH: f32 = sprite::CHARACTER_SIZE.0;
+const RELATIVE_PLAYER_HEIGHT: f32 = sprite::CHARACTER_SIZE.1;
+const INTERACT_REACH: f32 = 1.;
+
+/// [`Interactable`] is something a [`Player`] can use by walking up to it.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Interactable<'a> {
+    /// [`Interactable::Building`] is a [`Building`] that can be used.
+    Building(Building),
+    /// [`Interactable::Npc`] is an [`Npc`] that can be talked to.
+    Npc(&'a Npc),
+}
 
 /// [`Player`] is the main character of the game.
 pub struct Player {
     pub x: f32,
     pub y: f32,
     pub speed: f32,
+    pub damage: u32,
+    pub armor_rating: u32,
     pub sprite_id: String,
     pub animation: String,
     pub color: Color,
+    pub inventory: Inventory,
+    pub wallet: Wallet,
+    pub stats: Stats,
+    pub equipment: Equipment,
+    pub recovery: HitRecovery,
     is_walking: bool,
+    is_interacting: bool,
+    facing: (f32, f32),
     last_x: f32,
     last_y: f32,
+    attack_timer: f32,
 }
 
 impl Player {
     /// [`Player::new`] creates a new [`Player`].
     pub fn new(x: f32, y: f32) -> Self {
-        Self {
+        let mut player = Self {
             x,
             y,
-            speed: BASE_MOVEMENT_SPEED,
+            speed: 0.,
+            damage: 0,
+            armor_rating: 0,
             sprite_id: "player_base".to_owned(),
             animation: "player_walk".to_owned(),
             color: Color::White,
+            inventory: Inventory::default(),
+            wallet: Wallet::default(),
+            stats: Stats::default(),
+            equipment: Equipment::default(),
+            recovery: HitRecovery::default(),
             is_walking: false,
+            is_interacting: false,
+            facing: (0., 1.),
             last_x: x,
             last_y: y,
-        }
+            attack_timer: 0.,
+        };
+        player.recalculate_stats();
+        player
     }
 
-    /// [`Player::update`] will be called every frame to update the [`Player`] state.
-    pub fn update(&mut self) {
+    /// [`Player::update`] will be called every frame to update the [`Player`] state,
+    /// where being knocked back moves them through a [`Map`].
+    pub fn update(&mut self, map: &Map, frame_time: f32) {
+        self.attack_timer = (self.attack_timer - frame_time).max(0.);
+        let knockback = self.recovery.update(frame_time);
+        self.move_player(knockback, map);
         self.is_walking = self.last_x != self.x || self.last_y != self.y;
         self.last_x = self.x;
         self.last_y = self.y;
     }
 
-    /// [`Player::draw_player`] will draw a [`Player`] on the game screen using
-    /// a [`Render`] implementation.
-    pub fn draw_player<R: Render>(&self, renderer: &mut R) {
-        if self.is_walking {
-            renderer.draw_animation(self.x, self.y, "character_walk", &self.animation);
+    /// [`Player::gain_xp`] will add an `amount` of experience to a [`Player`] on an
+    /// [`XpCurve`], and return a [`LevelUp`] for every level gained.
+    pub fn gain_xp(&mut self, amount: u32, curve: &XpCurve) -> Vec<LevelUp> {
+        let level_ups = self.stats.gain_xp(amount, curve);
+        self.recalculate_stats();
+        level_ups
+    }
+
+    /// [`Player::recalculate_stats`] will update every value derived from a
+    /// [`Player`]'s [`Stats`] and [`Equipment`], and must be called whenever they
+    /// change.
+    pub fn recalculate_stats(&mut self) {
+        self.speed = self.stats.get_speed();
+        self.damage = self.stats.attributes.strength + self.equipment.get_damage();
+        self.armor_rating = self.equipment.get_armor_rating();
+        self.stats.health = self.stats.health.min(self.stats.get_max_health());
+        self.stats.stamina = self.stats.stamina.min(self.stats.get_max_stamina());
+    }
+
+    /// [`Player::equip_selected`] will move one of the selected [`Inventory`] item
+    /// into the [`Player`]'s [`Equipment`], putting back whatever it replaces.
+    pub fn equip_selected(&mut self) -> Result<(), EquipError> {
+        let item = self
+            .inventory
+            .get_selected_stack()
+            .map(|stack| stack.item.clone())
+            .ok_or(EquipError::NotEquippable)?;
+        let slot = item.slot.ok_or(EquipError::NotEquippable)?;
+        // make sure whatever is taken off has somewhere to go first
+        let mut inventory = self.inventory.clone();
+        inventory.remove_item(&item.id, 1);
+        if let Some(equipped) = self.equipment.get(slot)
+            && !inventory.add_item(equipped, 1)
+        {
+            return Err(EquipError::InventoryFull);
+        }
+        self.inventory = inventory;
+        self.equipment.equip(item)?;
+        self.recalculate_stats();
+        Ok(())
+    }
+
+    /// [`Player::unequip`] will move the item in a `slot` of the [`Player`]'s
+    /// [`Equipment`] back into their [`Inventory`].
+    pub fn unequip(&mut self, slot: EquipSlot) -> Result<(), EquipError> {
+        let item = self
+            .equipment
+            .get(slot)
+            .ok_or(EquipError::EmptySlot(slot))?;
+        if !self.inventory.add_item(item, 1) {
+            return Err(EquipError::InventoryFull);
+        }
+        self.equipment.unequip(slot);
+        self.recalculate_stats();
+        Ok(())
+    }
+
+    /// [`Player::draw_player`] will draw a [`Player`] where a [`Camera`] sees them
+    /// on the game screen using a [`Render`] implementation.
+    pub fn draw_player<R: Render>(&self, camera: &Camera, renderer: &mut R) {
+        let (x, y) = camera.world_to_screen(self.x, self.y);
+        if self.is_dead() {
+            renderer.stop_animation("character_walk");
+            renderer.draw_sprite(x, y, "remains");
+            return;
+        }
+        if self.recovery.is_hidden() {
+            return;
+        }
+        if self.is_interacting {
+            renderer.stop_animation("character_walk");
+            renderer.draw_sprite(x, y, "player_interact");
+        } else if self.is_walking {
+            renderer.draw_animation(x, y, "character_walk", &self.animation);
         } else {
             renderer.stop_animation("character_walk");
-            renderer.draw_sprite(self.x, self.y, &self.sprite_id);
+            renderer.draw_sprite(x, y, &self.sprite_id);
+        }
+        for overlay_sprite_id in self
+            .equipment
+            .items()
+            .filter_map(|item| item.overlay_sprite_id.as_deref())
+        {
+            renderer.draw_sprite(x, y, overlay_sprite_id);
+        }
+        if self.is_attacking() {
+            // the swing is drawn on the tile just in front of the player
+            let (center_x, center_y) = self.get_center();
+            let (swing_x, swing_y) = camera.world_to_screen(
+                center_x + self.facing.0 * (RELATIVE_PLAYER_WIDTH / 2. + 0.5) - 0.5,
+                center_y + self.facing.1 * (RELATIVE_PLAYER_HEIGHT / 2. + 0.5) - 0.5,
+            );
+            renderer.draw_sprite(swing_x, swing_y, "swing");
         }
     }
 
-    /// [`Player::move_player`] will move a player, as long as the [`Tile`] they
-    /// are moving to is a passable tile.
-    pub fn move_player(&mut self, new_position: (f32, f32), map: &Map) {
-        if let Some(tile) = map.get_tile(
-            if new_position.0 < 0. {
-                self.x + new_position.0
-            } else {
-                self.x + RELATIVE_PLAYER_WIDTH + new_position.0
-            },
-            if new_position.1 < 0. {
-                self.y - RELATIVE_PLAYER_HEIGHT + new_position.1
-            } else {
-                self.y + new_position.1
-            },
-        ) && tile.eq(&Tile::Floor)
-        {
-            self.update_sprite(new_position);
-            self.x += new_position.0;
-            self.y += new_position.1;
+    /// [`Player::attack`] will start an attack if the [`Player`] is alive and ready
+    /// to, and returns `true` if they attacked.
+    pub fn attack(&mut self) -> bool {
+        if self.is_dead() || self.attack_timer > 0. {
+            return false;
+        }
+        self.attack_timer = ATTACK_COOLDOWN;
+        true
+    }
+
+    /// [`Player::is_attacking`] returns `true` while an attack is being swung.
+    pub fn is_attacking(&self) -> bool {
+        self.attack_timer > ATTACK_COOLDOWN - SWING_TIME
+    }
+
+    /// [`Player::can_hit`] returns `true` if an area at (`x`, `y`) that is `width` by
+    /// `height` tiles is in front of a [`Player`] and within reach of an attack.
+    pub fn can_hit(&self, x: f32, y: f32, width: f32, height: f32) -> bool {
+        self.get_reach(x, y, width, height, ATTACK_REACH).is_some()
+    }
+
+    /// [`Player::take_hit`] will hurt a [`Player`] by a hit with `damage` that came
+    /// `from` an (`x`, `y`) coordinate, less what their armor stops, and knock them
+    /// back. It returns the damage taken, which is `0` while they are invulnerable.
+    pub fn take_hit(&mut self, damage: u32, from: (f32, f32)) -> u32 {
+        if self.is_dead() || self.recovery.is_invulnerable() {
+            return 0;
         }
+        let taken = combat::get_damage_taken(damage, self.armor_rating);
+        self.stats.health = self.stats.health.saturating_sub(taken);
+        self.recovery.hit(from, self.get_center());
+        taken
+    }
+
+    /// [`Player::is_dead`] returns `true` once a [`Player`] has no health left.
+    pub fn is_dead(&self) -> bool {
+        self.stats.health == 0
+    }
+
+    /// [`Player::get_bounds`] returns the (`x`, `y`, `width`, `height`) area a
+    /// [`Player`] takes up.
+    pub fn get_bounds(&self) -> (f32, f32, f32, f32) {
+        (
+            self.x,
+            self.y,
+            RELATIVE_PLAYER_WIDTH,
+            RELATIVE_PLAYER_HEIGHT,
+        )
+    }
+
+    /// [`Player::get_center`] returns the (`x`, `y`) coordinate of the middle of
+    /// a [`Player`].
+    pub fn get_center(&self) -> (f32, f32) {
+        (
+            self.x + RELATIVE_PLAYER_WIDTH / 2.,
+            self.y + RELATIVE_PLAYER_HEIGHT / 2.,
+        )
+    }
+
+    /// [`Player::set_interacting`] shows a [`Player`] using something while
+    /// `is_interacting` is `true`.
+    pub fn set_interacting(&mut self, is_interacting: bool) {
+        self.is_interacting = is_interacting;
     }
 
-    fn update_sprite(&mut self, new_position: (f32, f32)) {
-        if new_position.1 < 0. && self.sprite_id != "player_back" {
-            self.animation = "player_walk_up".to_string();
-            self.sprite_id = "player_back".to_string();
-        } else if new_position.0 > 0. && self.sprite_id != "player_right" {
-            self.animation = "player_walk_right".to_string();
-            self.sprite_id = "player_right".to_string();
-        } else if new_position.0 < 0. && self.sprite_id != "player_left" {
-            self.animation = "player_walk_left".to_string();
-            self.sprite_id = "player_left".to_string();
-        } else if new_position.0 == 0. && new_position.1 >= 0. && self.sprite_id != "player_base" {
-            self.animation = "player_walk".to_string();
-            self.sprite_id = "player_base".to_string();
+    /// [`Player::get_interactable`] returns [`Some`] [`Interactable`] building on a
+    /// [`Map`] or [`Npc`] with something to say that is closest to a [`Player`], in
+    /// front of them and within reach, and [`None`] otherwise.
+    pub fn get_interactable<'a>(&self, map: &Map, npcs: &'a [Npc]) -> Option<Interactable<'a>> {
+        let buildings = map
+            .buildings
+            .iter()
+            .filter(|b| b.building_type.is_interactable())
+            .filter_map(|b| {
+                self.get_reach(b.x, b.y, b.width, b.height, INTERACT_REACH)
+                    .map(|distance| (Interactable::Building(*b), distance))
+            });
+        let npcs = npcs.iter().filter(|n| n.dialog.is_some()).filter_map(|n| {
+            let (width, height) = n.get_size();
+            self.get_reach(n.x, n.y, width, height, INTERACT_REACH)
+                .map(|distance| (Interactable::Npc(n), distance))
+        });
+        buildings
+            .chain(npcs)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(interactable, _)| interactable)
+    }
+
+    // the distance to an area if it is in front of the player and within `reach`
+    fn get_reach(&self, x: f32, y: f32, width: f32, height: f32, reach: f32) -> Option<f32> {
+        let (center_x, center_y) = self.get_center();
+        let distance = combat::get_gap(self.get_bounds(), (x, y, width, height));
+        // the area is in front if its nearest point is ahead of the player
+        let nearest_x = center_x.clamp(x, x + width);
+        let nearest_y = center_y.clamp(y, y + height);
+        let ahead = (nearest_x - center_x) * self.facing.0 + (nearest_y - center_y) * self.facing.1;
+        (distance <= reach && ahead > 0.).then_some(distance)
+    }
+
+    /// [`Player::get_bottom`] returns the `y` coordinate of the feet of a [`Player`],
+    /// which decides what they are drawn in front of.
+    pub fn get_bottom(&self) -> f32 {
+        self.y + RELATIVE_PLAYER_HEIGHT
+    }
+
+    /// [`Player::move_player`] will move a player by a (`x`, `y`) amount, sliding
+    /// along anything in the way as described by [`Map::move_area`].
+    pub fn move_player(&mut self, new_position: (f32, f32), map: &Map) {
+        (self.x, self.y) = map.move_area(
+            self.x,
+            self.y,
+            (RELATIVE_PLAYER_WIDTH, RELATIVE_PLAYER_HEIGHT),
+            new_position,
+        );
+    }
+
+    /// [`Player::get_facing`] returns the (`x`, `y`) direction a [`Player`] faces,
+    /// which is what they interact with and attack.
+    pub fn get_facing(&self) -> (f32, f32) {
+        self.facing
+    }
+
+    /// [`Player::face`] will turn a [`Player`] towards a (`x`, `y`) `direction`,
+    /// picking the sprite and walk animation of whichever axis is strongest.
+    pub fn face(&mut self, direction: (f32, f32)) {
+        let (sprite_id, animation, facing) = if direction.0.abs() > direction.1.abs() {
+            if direction.0 > 0. {
+                ("player_right", "player_walk_right", (1., 0.))
+            } else {
+                ("player_left", "player_walk_left", (-1., 0.))
+            }
+        } else if direction.1 < 0. {
+            ("player_back", "player_walk_up", (0., -1.))
+        } else {
+            ("player_base", "player_walk", (0., 1.))
+        };
+        self.facing = facing;
+        if self.sprite_id != sprite_id {
+            self.sprite_id = sprite_id.to_owned();
+            self.animation = animation.to_owned();
         }
     }
 }
@@ -0,0 +1,28 @@
+/// [`Random`] is a small xorshift number generator. Seeding it from an id gives
+/// everything its own sequence of numbers, but the same one every time the game runs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Random {
+    seed: u32,
+}
+
+impl Random {
+    /// [`Random::from_id`] creates a new [`Random`] seeded from a hash of an `id`.
+    pub fn from_id(id: &str) -> Self {
+        Self {
+            seed: id
+                .bytes()
+                .fold(2_166_136_261, |hash, b| {
+                    (hash ^ b as u32).wrapping_mul(16_777_619)
+                })
+                .max(1),
+        }
+    }
+
+    /// [`Random::next_f32`] returns the next number from `0.` up to `1.`.
+    pub fn next_f32(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        (self.seed % 10_000) as f32 / 10_000.
+    }
+}
@@ -0,0 +1,326 @@
+use super::{
+    mode::Mode,
+    player::Player,
+    state::GameState,
+    stats::{Attributes, Stats},
+};
+use crate::{
+    data::record::{self, DataError, Record},
+    items::{
+        catalog::ItemCatalog,
+        currency::{Price, Wallet},
+        inventory::Inventory,
+    },
+    maps::map::Map,
+};
+use std::{fmt, path::Path};
+
+/// [`SAVE_PATH`] is where the game is saved to and loaded from.
+pub const SAVE_PATH: &str = "saves/save.txt";
+
+/// [`SAVE_VERSION`] is the version of the save file format. It must be increased
+/// whenever the format changes, so older save files are rejected instead of being
+/// read incorrectly.
+pub const SAVE_VERSION: u32 = 6;
+
+/// [`has_save`] returns `true` if there is a save file at `path`.
+pub fn has_save(path: &str) -> bool {
+    Path::new(path).is_file()
+}
+
+/// [`save_game`] writes the [`Player`], [`Map`] and flags of a [`GameState`] to a save
+/// file at `path`.
+pub fn save_game(state: &GameState, path: &str) -> Result<(), SaveError> {
+    let save = to_save_string(state);
+    let write_error = |error| SaveError::Io {
+        path: path.to_owned(),
+        error,
+    };
+    if let Some(directory) = Path::new(path).parent() {
+        std::fs::create_dir_all(directory).map_err(write_error)?;
+    }
+    std::fs::write(path, save).map_err(write_error)
+}
+
+/// [`to_save_string`] returns what [`save_game`] would write for a [`GameState`].
+pub fn to_save_string(state: &GameState) -> String {
+    let player = &state.player;
+    // the game carries on playing when it is loaded, whatever menu it was saved from
+    let mut save = format!("[save game]\nversion = {SAVE_VERSION}\n");
+    save += &format!("mode = {}\n", Mode::Playing.get_id());
+    for flag in &state.flags {
+        save += &format!("flag = {flag}\n");
+    }
+    save += "\n";
+
+    save += "[player player]\n";
+    save += &format!("x = {}\ny = {}\n", player.x, player.y);
+    let (facing_x, facing_y) = player.get_facing();
+    save += &format!("facing_x = {facing_x}\nfacing_y = {facing_y}\n");
+    save += &format!(
+        "sprite_id = {}\nanimation = {}\n",
+        player.sprite_id, player.animation
+    );
+    save += &format!("money = {}\n", player.wallet.get_balance().get_copper());
+    let stats = &player.stats;
+    save += &format!(
+        "level = {}\nxp = {}\nhealth = {}\nstamina = {}\n",
+        stats.level, stats.xp, stats.health, stats.stamina
+    );
+    let attributes = &stats.attributes;
+    save += &format!(
+        "strength = {}\nagility = {}\nvitality = {}\nendurance = {}\n",
+        attributes.strength, attributes.agility, attributes.vitality, attributes.endurance
+    );
+    for stack in player.inventory.stacks() {
+        save += &format!("item = {} {}\n", stack.item.id, stack.quantity);
+    }
+    for item in player.equipment.items() {
+        save += &format!("equip = {}\n", item.id);
+    }
+
+    save += "\n";
+    save += &state.map.to_record_string("map");
+    save
+}
+
+/// [`load_game`] reads a save file at `path` into a [`GameState`], which carries on in
+/// [`Mode::Playing`]. The [`GameState`] is only changed if the whole save file is valid.
+pub fn load_game(state: &mut GameState, path: &str) -> Result<(), SaveError> {
+    let records = record::read_records(path)?;
+    let save_record = find_record(&records, "save")?;
+    let version: u32 = save_record.parse_required("version")?;
+    if version != SAVE_VERSION {
+        return Err(SaveError::UnsupportedVersion {
+            found: version,
+            expected: SAVE_VERSION,
+        });
+    }
+    // menus aren't saved, so a game opening in one would show whatever was left in it
+    let mode: Mode = save_record.parse_required("mode")?;
+    if mode != Mode::Playing {
+        return Err(SaveError::Corrupt(format!(
+            "line {}: the game can't be loaded into `{}`",
+            save_record.get_line("mode"),
+            mode.get_id()
+        )));
+    }
+    let flags: Vec<String> = save_record
+        .get_all("flag")
+        .into_iter()
+        .map(|(flag, _)| flag.to_owned())
+        .collect();
+    let player = parse_player(find_record(&records, "player")?, &state.items)?;
+    let map = Map::from_record(find_record(&records, "map")?)
+        .map_err(|e| SaveError::Corrupt(e.to_string()))?;
+
+    state.mode = Mode::Playing;
+    state.player = player;
+    state.map = map;
+    state.flags = flags;
+    Ok(())
+}
+
+fn find_record<'a>(records: &'a [Record], kind: &str) -> Result<&'a Record, SaveError> {
+    records
+        .iter()
+        .find(|r| r.kind == kind)
+        .ok_or_else(|| SaveError::Corrupt(format!("the `[{kind}]` section is missing")))
+}
+
+fn parse_player(player_record: &Record, items: &ItemCatalog) -> Result<Player, SaveError> {
+    let mut player = Player::new(
+        player_record.parse_required("x")?,
+        player_record.parse_required("y")?,
+    );
+    // facing picks a sprite too, so the saved sprite is put back afterwards
+    player.face((
+        player_record.parse_required("facing_x")?,
+        player_record.parse_required("facing_y")?,
+    ));
+    player.sprite_id = player_record.require("sprite_id")?.to_owned();
+    player.animation = player_record.require("animation")?.to_owned();
+    player.wallet = Wallet::new(Price::from_copper(player_record.parse("money", 0)?));
+    player.stats = Stats {
+        level: player_record.parse_required("level")?,
+        xp: player_record.parse_required("xp")?,
+        health: player_record.parse_required("health")?,
+        stamina: player_record.parse_required("stamina")?,
+        attributes: Attributes {
+            strength: player_record.parse_required("strength")?,
+            agility: player_record.parse_required("agility")?,
+            vitality: player_record.parse_required("vitality")?,
+            endurance: player_record.parse_required("endurance")?,
+        },
+    };
+
+    let mut inventory = Inventory::default();
+    for (value, line) in player_record.get_all("item") {
+        let Some((item_id, quantity)) = value.split_once(' ') else {
+            return Err(SaveError::Corrupt(format!(
+                "line {line}: expected `item = id quantity`"
+            )));
+        };
+        let quantity: u32 = record::parse_value(quantity.trim(), "item", line)?;
+        let Some(item) = items.get(item_id) else {
+            return Err(SaveError::Corrupt(format!(
+                "line {line}: unknown item `{item_id}`"
+            )));
+        };
+        if !inventory.add_item(item, quantity) {
+            return Err(SaveError::Corrupt(format!(
+                "line {line}: too many items to fit in the inventory"
+            )));
+        }
+    }
+    player.inventory = inventory;
+
+    for (item_id, line) in player_record.get_all("equip") {
+        let Some(item) = items.get(item_id) else {
+            return Err(SaveError::Corrupt(format!(
+                "line {line}: unknown item `{item_id}`"
+            )));
+        };
+        if player.equipment.equip(item.clone()).is_err() {
+            return Err(SaveError::Corrupt(format!(
+                "line {line}: `{item_id}` can't be equipped"
+            )));
+        }
+    }
+    player.recalculate_stats();
+    Ok(player)
+}
+
+/// [`SaveError`] represents a problem saving or loading the game.
+#[derive(Debug)]
+pub enum SaveError {
+    /// [`SaveError::Io`] means the save file at `path` couldn't be written.
+    Io { path: String, error: std::io::Error },
+    /// [`SaveError::Data`] means the save file couldn't be read or parsed.
+    Data(DataError),
+    /// [`SaveError::UnsupportedVersion`] means the save file was made by a different
+    /// version of the game.
+    UnsupportedVersion { found: u32, expected: u32 },
+    /// [`SaveError::Corrupt`] means the save file is missing or has invalid data.
+    Corrupt(String),
+}
+
+impl From<DataError> for SaveError {
+    fn from(error: DataError) -> Self {
+        SaveError::Data(error)
+    }
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io { path, error } => write!(f, "Could not write `{path}`: {error}"),
+            SaveError::Data(error) => write!(f, "Could not read the save file: {error}"),
+            SaveError::UnsupportedVersion { found, expected } => write!(
+                f,
+                "The save file is version {found}, but only version {expected} is supported"
+            ),
+            SaveError::Corrupt(reason) => write!(f, "The save file is corrupt: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn facing_is_restored_with_its_sprite() {
+        let path = std::env::temp_dir().join("rust_and_ruin_facing_save.txt");
+        let path = path.to_str().unwrap();
+        let mut state = GameState::new();
+        state.player.face((-1., 0.));
+        save_game(&state, path).unwrap();
+
+        let mut loaded = GameState::new();
+        load_game(&mut loaded, path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(loaded.player.get_facing(), (-1., 0.));
+        assert_eq!(loaded.player.sprite_id, "player_left");
+        assert_eq!(loaded.player.animation, "player_walk_left");
+    }
+
+    // write a save of a new game, changed by `edit`, and try to load it
+    fn load_edited_save(
+        file_name: &str,
+        edit: impl Fn(String) -> String,
+    ) -> (GameState, Result<(), SaveError>) {
+        let path = std::env::temp_dir().join(file_name);
+        let path = path.to_str().unwrap();
+        std::fs::write(path, edit(to_save_string(&GameState::new()))).unwrap();
+
+        let mut state = GameState::new();
+        state.player.x = -1.;
+        let result = load_game(&mut state, path);
+        std::fs::remove_file(path).unwrap();
+        (state, result)
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let (state, result) = load_edited_save("rust_and_ruin_version_save.txt", |save| {
+            save.replace(&format!("version = {SAVE_VERSION}"), "version = 1")
+        });
+        assert!(matches!(
+            result,
+            Err(SaveError::UnsupportedVersion {
+                found: 1,
+                expected: SAVE_VERSION
+            })
+        ));
+        assert_eq!(state.player.x, -1.);
+    }
+
+    #[test]
+    fn corrupt_saves_are_rejected_without_changing_the_game() {
+        let (state, result) = load_edited_save("rust_and_ruin_section_save.txt", |save| {
+            save.replace("[player player]", "[hero player]")
+        });
+        assert!(matches!(result, Err(SaveError::Corrupt(reason)) if reason.contains("[player]")));
+        assert_eq!(state.player.x, -1.);
+
+        let (state, result) = load_edited_save("rust_and_ruin_item_save.txt", |save| {
+            save.replace(
+                "[player player]\n",
+                "[player player]\nitem = golden_idol 1\n",
+            )
+        });
+        assert!(
+            matches!(result, Err(SaveError::Corrupt(reason)) if reason.contains("golden_idol"))
+        );
+        assert_eq!(state.player.x, -1.);
+
+        let (_, result) = load_edited_save("rust_and_ruin_field_save.txt", |save| {
+            save.replace("[player player]\n", "[player player]\nlevel = high\n")
+        });
+        assert!(matches!(
+            result,
+            Err(SaveError::Data(DataError::InvalidField { field, .. })) if field == "level"
+        ));
+    }
+
+    #[test]
+    fn games_are_only_loaded_into_play() {
+        let mut state = GameState::new();
+        state.mode = Mode::Inventory;
+        assert!(to_save_string(&state).contains("mode = playing"));
+
+        let (state, result) = load_edited_save("rust_and_ruin_mode_save.txt", |save| {
+            save.replace("mode = playing", "mode = crafting")
+        });
+        assert!(matches!(result, Err(SaveError::Corrupt(reason)) if reason.contains("crafting")));
+        assert_ne!(state.mode, Mode::Crafting);
+
+        let (state, result) = load_edited_save("rust_and_ruin_playing_save.txt", |save| save);
+        assert!(result.is_ok());
+        assert_eq!(state.mode, Mode::Playing);
+        assert_eq!(state.player.x, GameState::new().player.x);
+    }
+}
@@ -0,0 +1,539 @@
+use super::mode::Mode;
+use crate::{
+    data::record::{self, DataError, Record},
+    events::{
+        gamepad::GamepadButton,
+        input_map::{Action, Binding, InputMap, InputMapError},
+    },
+    renderer::render::TILE_SIZE,
+};
+use std::{fmt, path::Path};
+
+/// [`SETTINGS_PATH`] is where the settings are saved to and loaded from.
+pub const SETTINGS_PATH: &str = "config/settings.txt";
+
+/// [`RESOLUTIONS`] are the window sizes the settings screen offers.
+pub const RESOLUTIONS: [Resolution; 4] = [
+    Resolution::new(1280, 720),
+    Resolution::new(1600, 900),
+    Resolution::new(1920, 1080),
+    Resolution::new(2560, 1440),
+];
+
+// the smallest and largest ui scale, and how much it changes in one step
+const MIN_UI_SCALE: f32 = 0.5;
+const MAX_UI_SCALE: f32 = 2.;
+const UI_SCALE_STEP: f32 = 0.25;
+// the loudest volume, and how much it changes in one step
+const MAX_VOLUME: u32 = 100;
+const VOLUME_STEP: u32 = 10;
+// how much the stick dead zone changes in one step, below a dead zone of 1
+const DEAD_ZONE_STEP: f32 = 0.05;
+const MAX_DEAD_ZONE: f32 = 0.95;
+
+/// [`Resolution`] is the `width` and `height` of the window in pixels, written as
+/// `1280x720`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Resolution {
+    /// [`Resolution::width`] is how many pixels wide the window is.
+    pub width: u32,
+    /// [`Resolution::height`] is how many pixels tall the window is.
+    pub height: u32,
+}
+
+impl Resolution {
+    /// [`Resolution::new`] creates a new [`Resolution`].
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+}
+
+impl std::str::FromStr for Resolution {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (width, height) = s.split_once('x').ok_or(())?;
+        let width: u32 = width.trim().parse().map_err(|_| ())?;
+        let height: u32 = height.trim().parse().map_err(|_| ())?;
+        if width == 0 || height == 0 {
+            return Err(());
+        }
+        Ok(Resolution::new(width, height))
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+/// [`Settings`] are the player's preferences, which are loaded before the window
+/// opens.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    /// [`Settings::resolution`] is the size the window opens at.
+    pub resolution: Resolution,
+    /// [`Settings::fullscreen`] is `true` if the window fills the screen.
+    pub fullscreen: bool,
+    /// [`Settings::high_dpi`] is `true` if the window draws at the full resolution of
+    /// high density displays.
+    pub high_dpi: bool,
+    /// [`Settings::ui_scale`] multiplies the size everything is drawn at.
+    pub ui_scale: f32,
+    /// [`Settings::volume`] is how loud the game is, from `0` to `100` percent. The
+    /// game has no sound yet, so it is only kept for when it does.
+    pub volume: u32,
+    /// [`Settings::input_map`] binds keys and gamepad buttons to each [`Action`],
+    /// where an [`Action`] can have more than one of each.
+    pub input_map: InputMap,
+}
+
+impl Settings {
+    /// [`Settings::load`] reads the first `[settings id]` record of the settings file
+    /// at `path`, where any missing field keeps its default. The defaults are used
+    /// if there is no settings file yet.
+    pub fn load(path: &str) -> Result<Self, SettingsError> {
+        if !Path::new(path).is_file() {
+            return Ok(Settings::default());
+        }
+        let records = record::read_records(path)?;
+        let Some(settings_record) = records.iter().find(|r| r.kind == "settings") else {
+            return Ok(Settings::default());
+        };
+        Settings::from_record(settings_record)
+    }
+
+    /// [`Settings::from_record`] parses [`Settings`] from a [`Record`], where any
+    /// missing field keeps its default. Keys are bound with `key = action binding`
+    /// fields and gamepad buttons with `pad = action button` fields, and the default
+    /// bindings are used for either if there are none.
+    pub fn from_record(settings_record: &Record) -> Result<Self, SettingsError> {
+        let default = Settings::default();
+        let mut input_map = InputMap::empty();
+        for (value, line) in settings_record.get_all("key") {
+            let (action, binding) = value.split_once(' ').unwrap_or((value, ""));
+            let action: Action = record::parse_value(action, "key", line)?;
+            let binding: Binding = record::parse_value(binding.trim(), "key", line)?;
+            input_map
+                .bind(action, binding)
+                .map_err(|error| SettingsError::InputMap { line, error })?;
+        }
+        for (value, line) in settings_record.get_all("pad") {
+            let (action, button) = value.split_once(' ').unwrap_or((value, ""));
+            let action: Action = record::parse_value(action, "pad", line)?;
+            let button: GamepadButton = record::parse_value(button.trim(), "pad", line)?;
+            input_map
+                .bind_button(action, button)
+                .map_err(|error| SettingsError::InputMap { line, error })?;
+        }
+        if input_map.bindings().is_empty() {
+            input_map.reset_bindings();
+        }
+        if input_map.buttons().is_empty() {
+            input_map.reset_buttons();
+        }
+        input_map.stick = settings_record.parse("stick", default.input_map.stick)?;
+        input_map.dead_zone = settings_record.parse("dead_zone", default.input_map.dead_zone)?;
+        let settings = Settings {
+            resolution: settings_record.parse("resolution", default.resolution)?,
+            fullscreen: settings_record.parse("fullscreen", default.fullscreen)?,
+            high_dpi: settings_record.parse("high_dpi", default.high_dpi)?,
+            ui_scale: settings_record.parse("ui_scale", default.ui_scale)?,
+            volume: settings_record.parse("volume", default.volume)?,
+            input_map,
+        };
+        let invalid_field = |field: &str, value: String| DataError::InvalidField {
+            line: settings_record.get_line(field),
+            field: field.to_owned(),
+            value,
+        };
+        if !(MIN_UI_SCALE..=MAX_UI_SCALE).contains(&settings.ui_scale) {
+            return Err(invalid_field("ui_scale", settings.ui_scale.to_string()).into());
+        }
+        if settings.volume > MAX_VOLUME {
+            return Err(invalid_field("volume", settings.volume.to_string()).into());
+        }
+        let dead_zone = settings.input_map.dead_zone;
+        if !(0. ..=MAX_DEAD_ZONE).contains(&dead_zone) {
+            return Err(invalid_field("dead_zone", dead_zone.to_string()).into());
+        }
+        Ok(settings)
+    }
+
+    /// [`Settings::save`] writes the [`Settings`] to a settings file at `path`.
+    pub fn save(&self, path: &str) -> Result<(), SettingsError> {
+        let write_error = |error| SettingsError::Io {
+            path: path.to_owned(),
+            error,
+        };
+        if let Some(directory) = Path::new(path).parent() {
+            std::fs::create_dir_all(directory).map_err(write_error)?;
+        }
+        std::fs::write(path, self.to_record_string("game")).map_err(write_error)
+    }
+
+    /// [`Settings::to_record_string`] returns a `[settings id]` record of the
+    /// [`Settings`], which [`Settings::from_record`] can read back.
+    pub fn to_record_string(&self, id: &str) -> String {
+        let mut settings_record = format!("[settings {id}]\n");
+        settings_record += &format!("resolution = {}\n", self.resolution);
+        settings_record += &format!("fullscreen = {}\n", self.fullscreen);
+        settings_record += &format!("high_dpi = {}\n", self.high_dpi);
+        settings_record += &format!("ui_scale = {}\n", self.ui_scale);
+        settings_record += &format!("volume = {}\n", self.volume);
+        for (action, binding) in self.input_map.bindings() {
+            settings_record += &format!("key = {} {binding}\n", action.get_id());
+        }
+        for (action, button) in self.input_map.buttons() {
+            settings_record += &format!("pad = {} {button}\n", action.get_id());
+        }
+        settings_record += &format!("stick = {}\n", self.input_map.stick);
+        settings_record += &format!("dead_zone = {}\n", self.input_map.dead_zone);
+        settings_record
+    }
+
+    /// [`Settings::get_tile_size`] returns the size of a tile in pixels once it is
+    /// scaled by [`Settings::ui_scale`].
+    pub fn get_tile_size(&self) -> f32 {
+        TILE_SIZE * self.ui_scale
+    }
+
+    /// [`Settings::get_value`] returns [`Some`] text describing the value of a
+    /// [`SettingsOption`], and [`None`] if it doesn't have one.
+    pub fn get_value(&self, option: SettingsOption) -> Option<String> {
+        let on_off = |is_on: bool| if is_on { "On" } else { "Off" }.to_owned();
+        match option {
+            SettingsOption::Resolution => Some(self.resolution.to_string()),
+            SettingsOption::Fullscreen => Some(on_off(self.fullscreen)),
+            SettingsOption::HighDpi => Some(on_off(self.high_dpi)),
+            SettingsOption::UiScale => Some(format!("{}%", (self.ui_scale * 100.).round())),
+            SettingsOption::Volume => Some(format!("{}%", self.volume)),
+            SettingsOption::DeadZone => {
+                Some(format!("{}%", (self.input_map.dead_zone * 100.).round()))
+            }
+            SettingsOption::Binding(action) => Some(self.input_map.describe(action)),
+            SettingsOption::Save | SettingsOption::Back => None,
+        }
+    }
+
+    /// [`Settings::change`] steps the value of a [`SettingsOption`] `step` times
+    /// forwards, or backwards if it is negative. Choices wrap around, while amounts
+    /// stay within their range.
+    pub fn change(&mut self, option: SettingsOption, step: i32) {
+        match option {
+            SettingsOption::Resolution => {
+                let count = RESOLUTIONS.len() as i32;
+                let index = RESOLUTIONS
+                    .iter()
+                    .position(|r| *r == self.resolution)
+                    .map_or(if step > 0 { -1 } else { 0 }, |i| i as i32);
+                self.resolution = RESOLUTIONS[(index + step).rem_euclid(count) as usize];
+            }
+            SettingsOption::Fullscreen if step % 2 != 0 => self.fullscreen = !self.fullscreen,
+            SettingsOption::HighDpi if step % 2 != 0 => self.high_dpi = !self.high_dpi,
+            SettingsOption::UiScale => {
+                self.ui_scale =
+                    (self.ui_scale + UI_SCALE_STEP * step as f32).clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+            }
+            SettingsOption::Volume => {
+                self.volume = (self.volume as i32 + (VOLUME_STEP as i32) * step)
+                    .clamp(0, MAX_VOLUME as i32) as u32;
+            }
+            SettingsOption::DeadZone => {
+                // stepping by a rounded number of steps keeps the value free of drift
+                let steps = (self.input_map.dead_zone / DEAD_ZONE_STEP).round() + step as f32;
+                self.input_map.dead_zone = (steps * DEAD_ZONE_STEP).clamp(0., MAX_DEAD_ZONE);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            resolution: RESOLUTIONS[0],
+            fullscreen: false,
+            high_dpi: true,
+            ui_scale: 1.,
+            volume: 80,
+            input_map: InputMap::default(),
+        }
+    }
+}
+
+/// [`SettingsOption`] is an entry of the settings screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SettingsOption {
+    /// [`SettingsOption::Resolution`] picks one of the [`RESOLUTIONS`].
+    Resolution,
+    /// [`SettingsOption::Fullscreen`] turns [`Settings::fullscreen`] on or off.
+    Fullscreen,
+    /// [`SettingsOption::HighDpi`] turns [`Settings::high_dpi`] on or off.
+    HighDpi,
+    /// [`SettingsOption::UiScale`] makes everything bigger or smaller.
+    UiScale,
+    /// [`SettingsOption::Volume`] makes the game louder or quieter.
+    Volume,
+    /// [`SettingsOption::DeadZone`] changes how far a gamepad stick has to be tilted
+    /// before it moves anything.
+    DeadZone,
+    /// [`SettingsOption::Binding`] binds the next key pressed to an [`Action`], in
+    /// place of its other keys.
+    Binding(Action),
+    /// [`SettingsOption::Save`] writes the changed settings to the settings file.
+    Save,
+    /// [`SettingsOption::Back`] leaves the settings screen, dropping unsaved changes.
+    Back,
+}
+
+impl SettingsOption {
+    /// [`SettingsOption::ALL`] is every [`SettingsOption`] in the order they are shown.
+    pub const ALL: [SettingsOption; 16] = [
+        SettingsOption::Resolution,
+        SettingsOption::Fullscreen,
+        SettingsOption::HighDpi,
+        SettingsOption::UiScale,
+        SettingsOption::Volume,
+        SettingsOption::DeadZone,
+        SettingsOption::Binding(Action::Up),
+        SettingsOption::Binding(Action::Down),
+        SettingsOption::Binding(Action::Left),
+        SettingsOption::Binding(Action::Right),
+        SettingsOption::Binding(Action::Interact),
+        SettingsOption::Binding(Action::Attack),
+        SettingsOption::Binding(Action::Inventory),
+        SettingsOption::Binding(Action::Pause),
+        SettingsOption::Save,
+        SettingsOption::Back,
+    ];
+
+    /// [`SettingsOption::needs_restart`] returns `true` if a change to a
+    /// [`SettingsOption`] only applies once the game is restarted, since it is used
+    /// to open the window or is handed to the renderer that reads input.
+    pub fn needs_restart(&self) -> bool {
+        matches!(
+            self,
+            SettingsOption::Resolution
+                | SettingsOption::Fullscreen
+                | SettingsOption::HighDpi
+                | SettingsOption::UiScale
+                | SettingsOption::DeadZone
+                | SettingsOption::Binding(_)
+        )
+    }
+
+    /// [`SettingsOption::has_effect`] returns `false` if a [`SettingsOption`] is saved
+    /// but doesn't change anything yet, like the volume of a game without sound.
+    pub fn has_effect(&self) -> bool {
+        !matches!(self, SettingsOption::Volume)
+    }
+}
+
+impl fmt::Display for SettingsOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsOption::Resolution => write!(f, "Resolution"),
+            SettingsOption::Fullscreen => write!(f, "Fullscreen"),
+            SettingsOption::HighDpi => write!(f, "High DPI"),
+            SettingsOption::UiScale => write!(f, "UI Scale"),
+            SettingsOption::Volume => write!(f, "Volume"),
+            SettingsOption::DeadZone => write!(f, "Dead Zone"),
+            SettingsOption::Binding(action) => write!(f, "{action}"),
+            SettingsOption::Save => write!(f, "Save"),
+            SettingsOption::Back => write!(f, "Back"),
+        }
+    }
+}
+
+/// [`SettingsMenu`] holds the state of the settings screen while it is open.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SettingsMenu {
+    /// [`SettingsMenu::selected`] is the index of the selected [`SettingsOption`].
+    pub selected: usize,
+    /// [`SettingsMenu::draft`] are the [`Settings`] being edited, which are only kept
+    /// once they are saved.
+    pub draft: Settings,
+    /// [`SettingsMenu::return_mode`] is the [`Mode`] to go back to when the settings
+    /// screen is closed.
+    pub return_mode: Mode,
+    /// [`SettingsMenu::rebinding`] is [`Some`] [`Action`] the next key pressed is bound
+    /// to, and [`None`] otherwise.
+    pub rebinding: Option<Action>,
+    // `true` on the frame a key was bound, so the rest of the input it made is ignored
+    has_bound_key: bool,
+}
+
+impl SettingsMenu {
+    /// [`SettingsMenu::new`] creates a new [`SettingsMenu`] editing a copy of
+    /// `settings`, which goes back to `return_mode` when it is closed.
+    pub fn new(settings: &Settings, return_mode: Mode) -> Self {
+        Self {
+            selected: 0,
+            draft: settings.clone(),
+            return_mode,
+            rebinding: None,
+            has_bound_key: false,
+        }
+    }
+
+    /// [`SettingsMenu::update`] will be called every frame to stop ignoring the input
+    /// made by a key that was just bound.
+    pub fn update(&mut self) {
+        self.has_bound_key = false;
+    }
+
+    /// [`SettingsMenu::is_rebinding`] returns `true` while input is meant for binding
+    /// a key to an [`Action`], rather than for the settings screen.
+    pub fn is_rebinding(&self) -> bool {
+        self.rebinding.is_some() || self.has_bound_key
+    }
+
+    /// [`SettingsMenu::bind_key`] binds a key to the [`SettingsMenu::rebinding`]
+    /// [`Action`] in place of its other keys, where a key bound to [`Action::Pause`]
+    /// cancels instead. An [`InputMapError`] is returned without changing anything if
+    /// the key is bound to another [`Action`].
+    pub fn bind_key(&mut self, binding: Binding) -> Result<(), InputMapError> {
+        let Some(action) = self.rebinding.take() else {
+            return Ok(());
+        };
+        self.has_bound_key = true;
+        let input_map = &mut self.draft.input_map;
+        if action != Action::Pause && input_map.get_action(binding) == Some(Action::Pause) {
+            return Ok(());
+        }
+        let mut rebound = input_map.clone();
+        for old_binding in rebound.get_bindings(action) {
+            rebound.unbind(old_binding);
+        }
+        rebound.bind(action, binding)?;
+        *input_map = rebound;
+        Ok(())
+    }
+
+    /// [`SettingsMenu::move_cursor`] moves the selected option by `y` rows, staying
+    /// within [`SettingsOption::ALL`].
+    pub fn move_cursor(&mut self, y: i32) {
+        let last = SettingsOption::ALL.len() as i32 - 1;
+        self.selected = (self.selected as i32 + y).clamp(0, last) as usize;
+    }
+
+    /// [`SettingsMenu::get_selected`] returns the selected [`SettingsOption`].
+    pub fn get_selected(&self) -> SettingsOption {
+        SettingsOption::ALL[self.selected.min(SettingsOption::ALL.len() - 1)]
+    }
+
+    /// [`SettingsMenu::is_restart_required`] returns `true` if the draft value of a
+    /// [`SettingsOption`] differs from the `launch_settings` the game was started
+    /// with, and only applies after a restart.
+    pub fn is_restart_required(&self, option: SettingsOption, launch_settings: &Settings) -> bool {
+        option.needs_restart() && self.draft.get_value(option) != launch_settings.get_value(option)
+    }
+}
+
+/// [`SettingsError`] represents a problem saving the settings.
+#[derive(Debug)]
+pub enum SettingsError {
+    /// [`SettingsError::Io`] means the settings file at `path` couldn't be written.
+    Io { path: String, error: std::io::Error },
+    /// [`SettingsError::Data`] means the settings file couldn't be read or parsed.
+    Data(DataError),
+    /// [`SettingsError::InputMap`] means a key or gamepad button on a `line` of the
+    /// settings file couldn't be bound.
+    InputMap { line: usize, error: InputMapError },
+}
+
+impl From<DataError> for SettingsError {
+    fn from(error: DataError) -> Self {
+        SettingsError::Data(error)
+    }
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Io { path, error } => write!(f, "Could not write `{path}`: {error}"),
+            SettingsError::Data(error) => write!(f, "Could not read the settings: {error}"),
+            SettingsError::InputMap { line, error } => {
+                write!(f, "Could not read the settings: line {line}: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{gamepad::Stick, input_map::Key};
+
+    // parse `source`, which holds a single `[settings id]` record
+    fn parse_settings(source: &str) -> Result<Settings, SettingsError> {
+        Settings::from_record(&record::parse_records(source).unwrap()[0])
+    }
+
+    #[test]
+    fn settings_are_read_back_as_they_were_written() {
+        let mut settings = Settings {
+            resolution: Resolution::new(1000, 700),
+            fullscreen: true,
+            high_dpi: false,
+            ui_scale: 1.5,
+            volume: 30,
+            input_map: InputMap::default(),
+        };
+        let mut binding = Binding::new(Key::Char('J'));
+        binding.modifiers.ctrl = true;
+        settings.input_map.bind(Action::Attack, binding).unwrap();
+        settings.input_map.stick = Stick::Right;
+        settings.input_map.dead_zone = 0.33;
+
+        let saved = settings.to_record_string("game");
+        assert_eq!(parse_settings(&saved).unwrap(), settings);
+    }
+
+    #[test]
+    fn missing_fields_and_bindings_keep_their_defaults() {
+        let settings = parse_settings("[settings game]\nvolume = 50\n").unwrap();
+        assert_eq!(
+            settings,
+            Settings {
+                volume: 50,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_settings_are_reported_on_their_line() {
+        let settings = parse_settings("[settings game]\nvolume = 50\nui_scale = 3\n");
+        assert!(matches!(
+            settings,
+            Err(SettingsError::Data(DataError::InvalidField { line: 3, field, .. }))
+                if field == "ui_scale"
+        ));
+
+        let settings = parse_settings("[settings game]\nkey = up W\nkey = down W\n");
+        assert!(matches!(
+            settings,
+            Err(SettingsError::InputMap {
+                line: 3,
+                error: InputMapError::Conflict { .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn stepping_a_custom_resolution_picks_a_listed_one() {
+        let mut settings = Settings {
+            resolution: Resolution::new(1000, 700),
+            ..Default::default()
+        };
+        settings.change(SettingsOption::Resolution, 1);
+        assert_eq!(settings.resolution, RESOLUTIONS[0]);
+        settings.change(SettingsOption::Resolution, -1);
+        assert_eq!(settings.resolution, RESOLUTIONS[3]);
+    }
+}
@@ -1,102 +1,866 @@
-use super::{mode::Mode, player::Player, screen::Screen};
+use super::{
+    combat,
+    crafting::CraftingMenu,
+    dialog::{Conversation, DIALOGS_PATH, DialogBook, DialogNode, END_NODE, Effect},
+    enemy::{self, ENEMIES_PATH, Enemy},
+    game_over::{GameOverMenu, GameOverOption, RESPAWN_PATH, RespawnPenalty},
+    mode::Mode,
+    movement::MovementController,
+    npc::{self, NPCS_PATH, Npc},
+    pause::{PauseMenu, PauseOption},
+    player::{Interactable, Player},
+    save::{self, SAVE_PATH},
+    screen::Screen,
+    settings::{SETTINGS_PATH, Settings, SettingsMenu, SettingsOption},
+    stats::{XP_CURVE_PATH, XpCurve},
+    title_menu::{TitleMenu, TitleOption},
+};
 use crate::{
-    events::{self, event::Event, event_listener::EventListener, input::InputEvent},
-    maps::{building::Building, building_type::BuildingType, map::Map, tile::Tile},
-    renderer::render::{Render, TILE_SIZE},
-    ui::interface::Interface,
+    events::{self, event::Event, event_listener::EventListener, gamepad, input::InputEvent},
+    items::{
+        catalog::{ITEMS_PATH, ItemCatalog},
+        currency::{Price, Wallet},
+        inventory::Inventory,
+        item::Item,
+        recipe::{RECIPES_PATH, RecipeBook},
+        world_item::{WORLD_ITEM_SIZE, WorldItem},
+    },
+    maps::{
+        building::Building,
+        building_type::BuildingType,
+        camera::Camera,
+        map::{Map, STARTING_MAP_PATH},
+    },
+    renderer::render::Render,
+    ui::{self, interface::Interface},
 };
 
+/// [`STARTING_ITEMS`] is the id and quantity of every item the player starts with.
+const STARTING_ITEMS: &[(&str, u32)] = &[("rusty_sword", 1), ("wood_plank", 10), ("iron_ore", 6)];
+
+/// [`STARTING_COPPER`] is how much money the player starts with.
+const STARTING_COPPER: u64 = 250;
+
+/// [`CRAFTING_XP`] is how much experience the player gains for crafting something.
+const CRAFTING_XP: u32 = 25;
+
+/// [`NOTIFICATION_TIME`] is how many seconds a notification stays on screen.
+const NOTIFICATION_TIME: f32 = 3.;
+
+/// [`GAME_OVER_DELAY`] is how many seconds the player lies fallen before the game
+/// over screen opens.
+const GAME_OVER_DELAY: f32 = 2.;
+
 /// [`GameState`] holds all stateful information about the game.
 pub struct GameState {
     pub mode: Mode,
     pub quit_game: bool,
     pub player: Player,
     pub map: Map,
+    pub camera: Camera,
+    pub movement: MovementController,
+    pub npcs: Vec<Npc>,
+    pub enemies: Vec<Enemy>,
+    pub world_items: Vec<WorldItem>,
+    pub items: ItemCatalog,
+    pub recipes: RecipeBook,
+    pub crafting: CraftingMenu,
+    pub dialogs: DialogBook,
+    pub conversation: Conversation,
+    pub flags: Vec<String>,
+    pub has_save: bool,
+    pub message: Option<String>,
+    pub notification: Option<String>,
+    pub xp_curve: XpCurve,
+    pub game_over: GameOverMenu,
+    pub title_menu: TitleMenu,
+    pub pause: PauseMenu,
+    pub settings: Settings,
+    pub settings_menu: SettingsMenu,
+    pub respawn_penalty: RespawnPenalty,
+    notification_timer: f32,
+    death_timer: f32,
+    saved_progress: Option<String>,
+    launch_settings: Settings,
+    held_inputs: Vec<InputEvent>,
+    last_held_inputs: Vec<InputEvent>,
 }
 
 impl GameState {
-    /// [`GameState::new`] will create a new [`GameState`].
+    /// [`GameState::new`] will create a new [`GameState`] with the default [`Settings`].
     pub fn new() -> Self {
-        let mut game_map = Map::new();
-        let crafting_bench_width = 121. / TILE_SIZE;
-        let crafting_bench_height = 48. / TILE_SIZE;
-        let crafting_bench = Building::new(
-            BuildingType::CraftingBench,
-            20.,
-            20.,
-            crafting_bench_width,
-            crafting_bench_height,
-        );
-
-        game_map.add_building(crafting_bench);
-        for x in 0..crafting_bench_height as usize {
-            for y in 0..crafting_bench_height as usize {
-                game_map.add_tile(Tile::Wall, 20. + x as f32, 20. + y as f32);
-            }
-        }
+        GameState::with_settings(Settings::default())
+    }
 
+    /// [`GameState::with_settings`] will create a new [`GameState`] with the
+    /// [`Settings`] the game was started with.
+    pub fn with_settings(settings: Settings) -> Self {
+        let game_map = Map::default();
         Self {
             mode: Mode::TitleScreen,
             quit_game: false,
-            player: Player::new(10., 10.),
+            player: Player::new(game_map.spawn.0, game_map.spawn.1),
             map: game_map,
+            camera: Camera::default(),
+            movement: MovementController::default(),
+            npcs: Vec::new(),
+            enemies: Vec::new(),
+            world_items: Vec::new(),
+            items: ItemCatalog::default(),
+            recipes: RecipeBook::default(),
+            crafting: CraftingMenu::default(),
+            dialogs: DialogBook::default(),
+            conversation: Conversation::default(),
+            flags: Vec::new(),
+            has_save: false,
+            message: None,
+            notification: None,
+            xp_curve: XpCurve::default(),
+            game_over: GameOverMenu::default(),
+            title_menu: TitleMenu::default(),
+            pause: PauseMenu::default(),
+            settings_menu: SettingsMenu::new(&settings, Mode::TitleScreen),
+            launch_settings: settings.clone(),
+            settings,
+            respawn_penalty: RespawnPenalty::default(),
+            notification_timer: 0.,
+            death_timer: 0.,
+            saved_progress: None,
+            held_inputs: Vec::new(),
+            last_held_inputs: Vec::new(),
+        }
+    }
+
+    /// [`GameState::load_game_assets`] will load all game data from `assets/`, and
+    /// place the [`Player`] on the starting [`Map`] with their starting items and money.
+    pub fn load_game_assets(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.items = ItemCatalog::load(ITEMS_PATH)?;
+        self.recipes = RecipeBook::load(RECIPES_PATH, &self.items)?;
+        self.dialogs = DialogBook::load(DIALOGS_PATH, &self.items)?;
+        self.xp_curve = XpCurve::load(XP_CURVE_PATH)?;
+        self.respawn_penalty = RespawnPenalty::load(RESPAWN_PATH)?;
+        self.map = Map::load(STARTING_MAP_PATH)?;
+        self.npcs = npc::load_npcs(NPCS_PATH, &self.map, &self.dialogs)?;
+        self.enemies = enemy::load_enemies(ENEMIES_PATH, &self.map, &self.items)?;
+        self.player = Player::new(self.map.spawn.0, self.map.spawn.1);
+        for (id, quantity) in STARTING_ITEMS {
+            if let Some(item) = self.items.get(id) {
+                self.player.inventory.add_item(item, *quantity);
+            }
         }
+        self.player.wallet = Wallet::new(Price::from_copper(STARTING_COPPER));
+        self.world_items.clear();
+        self.flags.clear();
+        self.has_save = save::has_save(SAVE_PATH);
+        Ok(())
     }
 
-    /// [`GameState::update`] will run every tick to update the state of the game.
-    pub fn update(&mut self) {
+    /// [`GameState::update`] will run every tick to update the state of the game,
+    /// where `frame_time` is the seconds since the last tick.
+    pub fn update(&mut self, frame_time: f32) {
+        self.last_held_inputs = std::mem::take(&mut self.held_inputs);
+        self.notification_timer -= frame_time;
+        if self.notification_timer <= 0. {
+            self.notification = None;
+        }
         match self.mode {
             Mode::TitleScreen => {}
             Mode::Playing => {
-                self.player.update();
-                // update player, enemies, world events etc.
-                // TODO: player gain XP to show status bar will change
-                // self.player.gain_xp(1);
+                if self.player.is_dead() {
+                    self.movement.stop();
+                    self.death_timer += frame_time;
+                    if self.death_timer >= GAME_OVER_DELAY {
+                        self.open_game_over();
+                    }
+                } else {
+                    self.movement
+                        .update(&mut self.player, &self.map, frame_time);
+                }
+                self.player.update(&self.map, frame_time);
+                let player_center = self.player.get_center();
+                for npc in &mut self.npcs {
+                    npc.update(&self.map, player_center, frame_time);
+                }
+                self.update_enemies(frame_time);
+                self.pick_up_items();
+                let (center_x, center_y) = player_center;
+                self.camera.follow(center_x, center_y, &self.map);
             }
+            Mode::Dialog => self.conversation.update(frame_time),
+            Mode::Settings => self.settings_menu.update(),
             _ => {}
         }
     }
+
+    /// [`GameState::gain_xp`] will give the [`Player`] an `amount` of experience,
+    /// and show a notification for every level they gain.
+    pub fn gain_xp(&mut self, amount: u32) {
+        for level_up in self.player.gain_xp(amount, &self.xp_curve) {
+            self.notify(level_up.to_string());
+        }
+    }
+
+    /// [`GameState::notify`] will show a `notification` on the status bar for a
+    /// few seconds.
+    pub fn notify(&mut self, notification: String) {
+        self.notification = Some(notification);
+        self.notification_timer = NOTIFICATION_TIME;
+    }
+
+    // move every enemy, let them attack the player, and clear away old remains
+    fn update_enemies(&mut self, frame_time: f32) {
+        let player_bounds = self.player.get_bounds();
+        let mut attacks: Vec<(u32, (f32, f32))> = Vec::new();
+        for enemy in &mut self.enemies {
+            if enemy.update(&self.map, player_bounds, frame_time) {
+                attacks.push((enemy.damage, enemy.get_center()));
+            }
+        }
+        for (damage, from) in attacks {
+            if self.player.take_hit(damage, from) > 0 && self.player.is_dead() {
+                self.notify("You have fallen...".to_owned())
+            }
+        }
+        self.enemies.retain(|e| !e.is_gone());
+    }
+
+    fn open_game_over(&mut self) {
+        self.death_timer = 0.;
+        // there is nothing to load without a save, so start on respawning
+        self.game_over = GameOverMenu::default();
+        if !self.has_save {
+            self.game_over.move_cursor(1);
+        }
+        self.mode = Mode::GameOver;
+    }
+
+    fn choose_game_over_option(&mut self, option: GameOverOption) {
+        match option {
+            GameOverOption::LoadSave if self.has_save => match save::load_game(self, SAVE_PATH) {
+                Ok(()) => {
+                    self.reset_world();
+                    self.mark_saved()
+                }
+                Err(e) => self.notify(e.to_string()),
+            },
+            GameOverOption::LoadSave => self.notify("There is no save to load".to_owned()),
+            GameOverOption::Respawn => self.respawn(),
+            GameOverOption::QuitToTitle => {
+                // start over from scratch, so continuing from the title is a new game
+                self.message = self.load_game_assets().err().map(|e| e.to_string());
+                self.movement.stop();
+                self.mode = Mode::TitleScreen;
+            }
+        }
+    }
+
+    // act on an entry of the title screen menu, if it can be chosen
+    fn choose_title_option(&mut self, option: TitleOption) {
+        if !option.is_enabled(self.has_save) {
+            return;
+        }
+        match option {
+            TitleOption::NewGame => match self.load_game_assets() {
+                Ok(()) => {
+                    self.message = None;
+                    self.mode = Mode::Playing;
+                    self.mark_saved()
+                }
+                Err(e) => self.message = Some(e.to_string()),
+            },
+            TitleOption::Continue => match save::load_game(self, SAVE_PATH) {
+                Ok(()) => {
+                    self.message = None;
+                    self.reset_world();
+                    self.mode = Mode::Playing;
+                    self.mark_saved()
+                }
+                Err(e) => self.message = Some(e.to_string()),
+            },
+            TitleOption::Settings => self.open_settings(),
+            TitleOption::Credits => self.title_menu.is_showing_credits = true,
+            TitleOption::Quit => self.quit_game = true,
+        }
+    }
+
+    // stop the world under the pause menu, noting if there is anything to lose by quitting
+    fn pause_game(&mut self) {
+        self.movement.stop();
+        self.pause = PauseMenu::new(self.has_unsaved_progress());
+        self.mode = Mode::Paused;
+    }
+
+    // act on an entry of the pause menu, warning before unsaved progress is lost
+    fn choose_pause_option(&mut self, option: PauseOption) {
+        if option.is_quitting() && !self.pause.confirm_quit() {
+            return;
+        }
+        match option {
+            PauseOption::Resume => self.mode = Mode::Playing,
+            PauseOption::Save => match save::save_game(self, SAVE_PATH) {
+                Ok(()) => {
+                    self.has_save = true;
+                    self.mark_saved();
+                    self.pause.has_unsaved_progress = false;
+                    self.notify("Game saved".to_owned())
+                }
+                Err(e) => self.notify(e.to_string()),
+            },
+            PauseOption::Settings => self.open_settings(),
+            PauseOption::QuitToTitle => {
+                // start over from scratch, so a new game from the title starts fresh
+                self.message = self.load_game_assets().err().map(|e| e.to_string());
+                self.mode = Mode::TitleScreen;
+            }
+            PauseOption::QuitToDesktop => self.quit_game = true,
+        }
+    }
+
+    // edit a copy of the settings on top of the current screen
+    fn open_settings(&mut self) {
+        self.settings_menu = SettingsMenu::new(&self.settings, self.mode);
+        self.mode = Mode::Settings;
+    }
+
+    // act on an entry of the settings screen, where a setting steps to its next value
+    // and a binding waits for a key
+    fn choose_settings_option(&mut self, option: SettingsOption) {
+        match option {
+            SettingsOption::Save => match self.settings_menu.draft.save(SETTINGS_PATH) {
+                Ok(()) => {
+                    self.settings = self.settings_menu.draft.clone();
+                    let needs_restart = SettingsOption::ALL.iter().any(|o| {
+                        self.settings_menu
+                            .is_restart_required(*o, &self.launch_settings)
+                    });
+                    self.notify(if needs_restart {
+                        "Settings saved, restart the game to apply them".to_owned()
+                    } else {
+                        "Settings saved".to_owned()
+                    })
+                }
+                Err(e) => self.notify(e.to_string()),
+            },
+            SettingsOption::Back => self.mode = self.settings_menu.return_mode,
+            SettingsOption::Binding(action) => self.settings_menu.rebinding = Some(action),
+            _ => self.settings_menu.draft.change(option, 1),
+        }
+    }
+
+    /// [`GameState::get_launch_settings`] returns the [`Settings`] the game was
+    /// started with, which are the ones in use until a restart.
+    pub fn get_launch_settings(&self) -> &Settings {
+        &self.launch_settings
+    }
+
+    /// [`GameState::draw_world_layer`] draws the world beneath the overlay of the
+    /// current mode.
+    pub fn draw_world_layer<R: Render>(&self, renderer: &mut R) {
+        ui::playing::draw_world(
+            &self.map,
+            &self.player,
+            &self.npcs,
+            &self.enemies,
+            &self.world_items,
+            &self.camera,
+            renderer,
+        );
+    }
+
+    /// [`GameState::has_unsaved_progress`] returns `true` if the game has changed since
+    /// it was last saved or loaded.
+    pub fn has_unsaved_progress(&self) -> bool {
+        self.saved_progress.as_deref() != Some(save::to_save_string(self).as_str())
+    }
+
+    // remember what the game looked like when it was last saved or loaded
+    fn mark_saved(&mut self) {
+        self.saved_progress = Some(save::to_save_string(self));
+    }
+
+    // find the title screen entry at a tile coordinate, with the menu centered on the screen
+    fn handle_title_screen_click(&self, x: f32, y: f32) -> Event {
+        events::title_screen::handle_title_screen_click(x, y, self.camera.width, self.camera.height)
+    }
+
+    // put every enemy back and clear the ground, i.e.: after loading a save
+    fn reset_world(&mut self) {
+        self.world_items.clear();
+        self.movement.stop();
+        match enemy::load_enemies(ENEMIES_PATH, &self.map, &self.items) {
+            Ok(enemies) => self.enemies = enemies,
+            Err(e) => self.notify(e.to_string()),
+        }
+    }
+
+    // bring the player back at the spawn point, paying the respawn penalty
+    fn respawn(&mut self) {
+        let penalty = self.respawn_penalty;
+        let lost = self
+            .player
+            .wallet
+            .get_balance()
+            .fraction(penalty.money_lost);
+        let mut notification = match self.player.wallet.spend(lost) {
+            Ok(()) => format!("You lost {lost}"),
+            Err(e) => e.to_string(),
+        };
+
+        if penalty.drop_items && !self.player.inventory.stacks().is_empty() {
+            // leave the inventory around where the player fell
+            let capacity = self.player.inventory.capacity();
+            let dropped = std::mem::replace(&mut self.player.inventory, Inventory::new(capacity));
+            let items = dropped
+                .stacks()
+                .iter()
+                .map(|stack| (stack.item.clone(), stack.quantity))
+                .collect();
+            self.drop_items(items, self.player.x, self.player.get_bottom() - 1.);
+            notification += " and left your items where you fell";
+        }
+
+        let stats = &mut self.player.stats;
+        stats.health = stats.get_max_health();
+        stats.stamina = stats.get_max_stamina();
+        self.player.recovery = Default::default();
+        (self.player.x, self.player.y) = self.map.spawn;
+        self.player.set_interacting(false);
+        self.movement.stop();
+        self.mode = Mode::Playing;
+        self.notify(notification);
+    }
+
+    // swing at every enemy in front of the player
+    fn attack(&mut self) {
+        if !self.player.attack() {
+            return;
+        }
+        let (damage, from) = (self.player.damage, self.player.get_center());
+        let mut defeated: Vec<usize> = Vec::new();
+        for (index, enemy) in self.enemies.iter_mut().enumerate() {
+            let (x, y, width, height) = enemy.get_bounds();
+            if self.player.can_hit(x, y, width, height)
+                && enemy.take_hit(damage, from) > 0
+                && enemy.is_dead()
+            {
+                defeated.push(index);
+            }
+        }
+        for index in defeated {
+            self.defeat_enemy(index);
+        }
+    }
+
+    // drop the loot of a defeated enemy where it fell, and reward the player
+    fn defeat_enemy(&mut self, index: usize) {
+        let enemy = &mut self.enemies[index];
+        let loot = enemy.roll_loot();
+        let (name, xp) = (enemy.name.clone(), enemy.xp);
+        let (x, y) = (enemy.x, enemy.get_bottom() - 1.);
+        let items = loot
+            .into_iter()
+            .filter_map(|(item_id, quantity)| {
+                self.items
+                    .get(&item_id)
+                    .map(|item| (item.clone(), quantity))
+            })
+            .collect();
+        self.drop_items(items, x, y);
+        self.notify(format!("Defeated {name}"));
+        self.gain_xp(xp);
+    }
+
+    // leave items in the world around an (`x`, `y`) coordinate, each on the nearest
+    // passable spot not already holding one, so none end up inside a wall or building
+    fn drop_items(&mut self, items: Vec<(Item, u32)>, x: f32, y: f32) {
+        for (item, quantity) in items {
+            let is_free = |x: f32, y: f32| {
+                !self.world_items.iter().any(|world_item| {
+                    (world_item.x - x).abs() < WORLD_ITEM_SIZE
+                        && (world_item.y - y).abs() < WORLD_ITEM_SIZE
+                })
+            };
+            // a map without room anywhere has nowhere better to put it
+            let (x, y) = self
+                .map
+                .find_passable_area(x, y, (WORLD_ITEM_SIZE, WORLD_ITEM_SIZE), is_free)
+                .unwrap_or((x, y));
+            self.world_items.push(WorldItem::new(item, quantity, x, y));
+        }
+    }
+
+    // pick up whatever the player is standing on that fits in their inventory
+    fn pick_up_items(&mut self) {
+        let player_bounds = self.player.get_bounds();
+        let inventory = &mut self.player.inventory;
+        let mut notification: Option<String> = None;
+        self.world_items.retain(|world_item| {
+            if combat::get_gap(player_bounds, world_item.get_bounds()) > 0. {
+                return true;
+            }
+            if inventory.add_item(&world_item.item, world_item.quantity) {
+                notification = Some(format!(
+                    "Picked up {}x {}",
+                    world_item.quantity, world_item.item.name
+                ));
+                false
+            } else {
+                notification = Some(format!("No room for {}", world_item.item.name));
+                true
+            }
+        });
+        if let Some(notification) = notification {
+            self.notify(notification)
+        }
+    }
+
+    // open whatever screen a building is used through
+    fn use_building(&mut self, building: Building) {
+        if let Some(dialog) = self.dialogs.get_for_building(building.building_type) {
+            let dialog_id = dialog.id.clone();
+            self.start_dialog(&dialog_id);
+            return;
+        }
+        match building.building_type {
+            BuildingType::CraftingBench => {
+                self.crafting = CraftingMenu::new(Some(BuildingType::CraftingBench));
+                self.movement.stop();
+                self.player.set_interacting(true);
+                self.mode = Mode::Crafting
+            }
+            BuildingType::Default => {}
+        }
+    }
+
+    // start a dialog by id, which keeps the world still until it ends
+    fn start_dialog(&mut self, dialog_id: &str) {
+        let Some(dialog) = self.dialogs.get(dialog_id) else {
+            return;
+        };
+        let start = dialog.start.clone();
+        self.movement.stop();
+        self.conversation = Conversation::new(dialog_id, &start);
+        self.mode = Mode::Dialog;
+        self.go_to_node(&start);
+    }
+
+    // show a node of the current dialog and apply its effects, or end the dialog
+    fn go_to_node(&mut self, node_id: &str) {
+        let node = self
+            .dialogs
+            .get(&self.conversation.dialog_id)
+            .and_then(|d| d.get_node(node_id))
+            .filter(|_| node_id != END_NODE)
+            .cloned();
+        let Some(node) = node else {
+            self.player.set_interacting(false);
+            self.mode = Mode::Playing;
+            return;
+        };
+        self.conversation = Conversation::new(&self.conversation.dialog_id, node_id);
+        for effect in node.effects {
+            self.apply_effect(effect);
+        }
+    }
+
+    fn get_dialog_node(&self) -> Option<&DialogNode> {
+        self.dialogs
+            .get(&self.conversation.dialog_id)?
+            .get_node(&self.conversation.node_id)
+    }
+
+    // finish typing out the text, or go where the choice at `index` leads
+    fn confirm_dialog(&mut self, index: usize) {
+        let Some(node) = self.get_dialog_node().cloned() else {
+            self.go_to_node(END_NODE);
+            return;
+        };
+        if !self.conversation.is_revealed(&node.text) {
+            self.conversation.reveal(&node.text);
+            return;
+        }
+        let choices = node.get_choices(&self.player, &self.flags);
+        let next = if choices.is_empty() {
+            node.next.unwrap_or_else(|| END_NODE.to_owned())
+        } else {
+            match choices.get(index) {
+                Some(choice) => choice.next.clone(),
+                None => return,
+            }
+        };
+        self.go_to_node(&next);
+    }
+
+    fn apply_effect(&mut self, effect: Effect) {
+        match effect {
+            Effect::GiveItem { item_id, quantity } => {
+                if let Some(item) = self.items.get(&item_id) {
+                    let name = item.name.clone();
+                    if self.player.inventory.add_item(item, quantity) {
+                        self.notify(format!("Received {quantity}x {name}"))
+                    } else {
+                        self.notify(format!("No room for {quantity}x {name}"))
+                    }
+                }
+            }
+            Effect::TakeItem { item_id, quantity } => {
+                self.player.inventory.remove_item(&item_id, quantity);
+            }
+            Effect::GiveMoney(copper) => {
+                if let Err(e) = self.player.wallet.add(Price::from_copper(copper)) {
+                    self.notify(e.to_string())
+                }
+            }
+            Effect::TakeMoney(copper) => {
+                if let Err(e) = self.player.wallet.spend(Price::from_copper(copper)) {
+                    self.notify(e.to_string())
+                }
+            }
+            Effect::GiveXp(amount) => self.gain_xp(amount),
+            Effect::SetFlag(flag) => {
+                if !self.flags.contains(&flag) {
+                    self.flags.push(flag)
+                }
+            }
+            Effect::ClearFlag(flag) => self.flags.retain(|f| *f != flag),
+        }
+    }
+
+    // directional input is sent every frame while held, so remember it to
+    // tell a new press apart from a held key when navigating menus
+    fn is_held_input(&mut self, input: InputEvent) -> bool {
+        match input {
+            InputEvent::Up | InputEvent::Down | InputEvent::Left | InputEvent::Right => {
+                self.held_inputs.push(input);
+                self.last_held_inputs.contains(&input)
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Screen for GameState {
     fn draw_screen<R: Render>(&self, renderer: &mut R) {
-        self.mode.draw_interface(&self.map, &self.player, renderer);
+        self.mode.draw_interface(self, renderer);
     }
 
-    fn handle_input(&mut self, input: InputEvent, frame_time: f32) {
+    fn handle_input(&mut self, input: InputEvent, _frame_time: f32) {
+        // outside of play, a tilted stick steps through menus like the arrow keys
+        let input = match input {
+            InputEvent::Move { x, y } if !matches!(self.mode, Mode::Playing) => {
+                gamepad::get_stick_direction(x, y)
+            }
+            _ => input,
+        };
+        let is_held_input = self.is_held_input(input);
         match self.mode {
             Mode::TitleScreen => match Mode::TitleScreen.handle_input(input) {
-                Event::Continue => self.mode = Mode::Playing,
-                Event::Quit => self.quit_game = true,
+                // any confirming input closes the credits again
+                event if self.title_menu.is_showing_credits => {
+                    if matches!(
+                        event,
+                        Event::Confirm | Event::Back | Event::LeftClicked { .. }
+                    ) {
+                        self.title_menu.is_showing_credits = false;
+                    }
+                }
+                Event::MoveCursor { y, .. } if !is_held_input => {
+                    self.title_menu.move_cursor(y, self.has_save)
+                }
+                Event::Hovered { x, y } => {
+                    if let Event::Select(index) = self.handle_title_screen_click(x, y) {
+                        self.title_menu.select(index, self.has_save);
+                    }
+                }
+                Event::LeftClicked { x, y } => {
+                    if let Event::Select(index) = self.handle_title_screen_click(x, y)
+                        && self.title_menu.select(index, self.has_save)
+                    {
+                        self.choose_title_option(self.title_menu.get_selected());
+                    }
+                }
+                Event::Confirm => self.choose_title_option(self.title_menu.get_selected()),
+                Event::Back => self.quit_game = true,
                 _ => {}
             },
             Mode::Playing => match Mode::Playing.handle_input(input) {
-                Event::MovePlayerForward => self
-                    .player
-                    .move_player(((self.player.speed * frame_time), 0.), &self.map),
-                Event::MovePlayerBackward => self
-                    .player
-                    .move_player((-(self.player.speed * frame_time), 0.), &self.map),
-                Event::MovePlayerUp => self
-                    .player
-                    .move_player((0., -(self.player.speed * 0.75 * frame_time)), &self.map),
-                Event::MovePlayerDown => self
-                    .player
-                    .move_player((0., (self.player.speed * 0.75 * frame_time)), &self.map),
+                // nothing is left to do after falling but wait for the game over screen
+                _ if self.player.is_dead() => {}
+                Event::MovePlayerForward => self.movement.push_direction(1., 0.),
+                Event::MovePlayerBackward => self.movement.push_direction(-1., 0.),
+                Event::MovePlayerUp => self.movement.push_direction(0., -1.),
+                Event::MovePlayerDown => self.movement.push_direction(0., 1.),
+                Event::MovePlayer { x, y } => self.movement.push_direction(x, y),
                 Event::LeftClicked { x, y } => {
-                    if let Event::Craft = events::playing::handle_playing_click(&self.map, x, y) {
-                        self.mode = Mode::Crafting
+                    let (world_x, world_y) = self.camera.screen_to_world(x, y);
+                    if let Event::Craft =
+                        events::playing::handle_playing_click(&self.map, world_x, world_y)
+                        && let Some(building) = self.map.get_building(world_x, world_y)
+                    {
+                        self.use_building(building)
+                    }
+                }
+                Event::RightClicked { x, y } => {
+                    let (world_x, world_y) = self.camera.screen_to_world(x, y);
+                    let (center_x, center_y) = self.player.get_center();
+                    self.player.face((world_x - center_x, world_y - center_y));
+                    self.attack()
+                }
+                Event::Attack => self.attack(),
+                Event::Interact => match self.player.get_interactable(&self.map, &self.npcs) {
+                    Some(Interactable::Building(building)) => self.use_building(building),
+                    Some(Interactable::Npc(npc)) => {
+                        if let Some(dialog_id) = npc.dialog.clone() {
+                            self.start_dialog(&dialog_id)
+                        }
                     }
+                    None => {}
+                },
+                Event::OpenInventory => {
+                    self.movement.stop();
+                    self.mode = Mode::Inventory
                 }
-                Event::Quit => self.quit_game = true,
+                Event::Pause => self.pause_game(),
                 _ => {}
             },
             Mode::Crafting => match Mode::Crafting.handle_input(input) {
+                Event::MoveCursor { y, .. } if !is_held_input => {
+                    self.crafting.move_cursor(y, self.recipes.recipes().len())
+                }
+                Event::LeftClicked { x, y } => {
+                    if let Event::Select(index) =
+                        events::crafting::handle_crafting_click(&self.recipes, x, y)
+                    {
+                        self.crafting.selected = index
+                    }
+                }
+                Event::Confirm => {
+                    let crafted = self.crafting.craft_selected(
+                        &self.recipes,
+                        &self.items,
+                        &mut self.player.inventory,
+                    );
+                    if crafted {
+                        self.gain_xp(CRAFTING_XP)
+                    }
+                }
+                Event::Back => {
+                    self.player.set_interacting(false);
+                    self.mode = Mode::Playing
+                }
+                _ => {}
+            },
+            Mode::Inventory => match Mode::Inventory.handle_input(input) {
+                Event::MoveCursor { x, y } if !is_held_input => {
+                    self.player
+                        .inventory
+                        .move_cursor(x, y, ui::inventory::GRID_COLUMNS)
+                }
+                Event::LeftClicked { x, y } => {
+                    match events::inventory::handle_inventory_click(&self.player.inventory, x, y) {
+                        Event::Select(slot) => self.player.inventory.select(slot),
+                        Event::Unequip(slot) => {
+                            if let Err(e) = self.player.unequip(slot) {
+                                self.notify(e.to_string())
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Confirm => {
+                    if let Err(e) = self.player.equip_selected() {
+                        self.notify(e.to_string())
+                    }
+                }
+                Event::Back => self.mode = Mode::Playing,
+                _ => {}
+            },
+            Mode::Dialog => match Mode::Dialog.handle_input(input) {
+                Event::MoveCursor { y, .. } if !is_held_input => {
+                    let choice_count = self
+                        .get_dialog_node()
+                        .map_or(0, |n| n.get_choices(&self.player, &self.flags).len());
+                    self.conversation.move_cursor(y, choice_count)
+                }
+                Event::LeftClicked { x, y } => {
+                    let Some(node) = self.get_dialog_node() else {
+                        return;
+                    };
+                    let choice_count = node.get_choices(&self.player, &self.flags).len();
+                    if let Event::Select(index) =
+                        events::dialog::handle_dialog_click(&node.text, choice_count, x, y)
+                    {
+                        self.confirm_dialog(index)
+                    }
+                }
+                Event::Confirm => self.confirm_dialog(self.conversation.selected),
+                Event::Back => self.go_to_node(END_NODE),
+                _ => {}
+            },
+            Mode::GameOver => match Mode::GameOver.handle_input(input) {
+                Event::MoveCursor { y, .. } if !is_held_input => self.game_over.move_cursor(y),
+                Event::LeftClicked { x, y } => {
+                    if let Event::Select(index) = events::game_over::handle_game_over_click(x, y) {
+                        self.game_over.selected = index;
+                        self.choose_game_over_option(self.game_over.get_selected())
+                    }
+                }
+                Event::Confirm => self.choose_game_over_option(self.game_over.get_selected()),
+                _ => {}
+            },
+            Mode::Paused => match Mode::Paused.handle_input(input) {
+                Event::MoveCursor { y, .. } if !is_held_input => self.pause.move_cursor(y),
+                Event::Hovered { x, y } => {
+                    if let Event::Select(index) = events::paused::handle_paused_click(x, y) {
+                        self.pause.select(index)
+                    }
+                }
+                Event::LeftClicked { x, y } => {
+                    if let Event::Select(index) = events::paused::handle_paused_click(x, y) {
+                        self.pause.select(index);
+                        self.choose_pause_option(self.pause.get_selected())
+                    }
+                }
+                Event::Confirm => self.choose_pause_option(self.pause.get_selected()),
                 Event::Back => self.mode = Mode::Playing,
-                Event::Quit => self.quit_game = true,
                 _ => {}
             },
-            Mode::Inventory => todo!(),
+            // while a key is being bound, the keys pressed are only for binding it
+            Mode::Settings if self.settings_menu.is_rebinding() => match input {
+                InputEvent::KeyPressed(binding) => {
+                    if let Err(e) = self.settings_menu.bind_key(binding) {
+                        self.notify(e.to_string())
+                    }
+                }
+                _ if matches!(Mode::Settings.handle_input(input), Event::Back) => {
+                    self.settings_menu.rebinding = None
+                }
+                _ => {}
+            },
+            Mode::Settings => match Mode::Settings.handle_input(input) {
+                Event::MoveCursor { x, y } if !is_held_input => {
+                    self.settings_menu.move_cursor(y);
+                    // only step a value sideways, so landing on a row leaves it as it is
+                    if x != 0 {
+                        let selected = self.settings_menu.get_selected();
+                        self.settings_menu.draft.change(selected, x)
+                    }
+                }
+                Event::Hovered { x, y } => {
+                    if let Event::Select(index) = events::settings::handle_settings_click(x, y) {
+                        self.settings_menu.selected = index
+                    }
+                }
+                Event::LeftClicked { x, y } => {
+                    if let Event::Select(index) = events::settings::handle_settings_click(x, y) {
+                        self.settings_menu.selected = index;
+                        self.choose_settings_option(self.settings_menu.get_selected())
+                    }
+                }
+                Event::Confirm => self.choose_settings_option(self.settings_menu.get_selected()),
+                Event::Back => self.mode = self.settings_menu.return_mode,
+                _ => {}
+            },
         };
     }
 }
@@ -106,3 +870,105 @@ impl Default for GameState {
         GameState::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        events::input_map::{Action, Binding, Key},
+        game::settings::Resolution,
+    };
+
+    // open the settings screen and choose the binding of an `action`
+    fn start_rebinding(action: Action) -> GameState {
+        let mut state = GameState::new();
+        state.open_settings();
+        state.settings_menu.selected = SettingsOption::ALL
+            .iter()
+            .position(|option| *option == SettingsOption::Binding(action))
+            .unwrap();
+        state.handle_input(InputEvent::Interact, 0.);
+        state.update(0.);
+        state
+    }
+
+    #[test]
+    fn moving_through_settings_leaves_the_draft_unchanged() {
+        let mut settings = Settings {
+            resolution: Resolution::new(1000, 700),
+            ..Default::default()
+        };
+        settings.input_map.dead_zone = 0.33;
+        let mut state = GameState::with_settings(settings.clone());
+        state.open_settings();
+
+        // visit every row on the way down and back up
+        let rows = SettingsOption::ALL.len();
+        let moves = std::iter::repeat_n(InputEvent::Down, rows);
+        for input in moves.chain(std::iter::repeat_n(InputEvent::Up, rows)) {
+            state.handle_input(input, 0.);
+            state.update(0.);
+        }
+        assert_eq!(state.settings_menu.draft, settings);
+
+        state.handle_input(InputEvent::Right, 0.);
+        assert_ne!(state.settings_menu.draft.resolution, settings.resolution);
+    }
+
+    #[test]
+    fn the_next_key_pressed_is_bound_in_place_of_the_others() {
+        let mut state = start_rebinding(Action::Up);
+        assert_eq!(state.settings_menu.rebinding, Some(Action::Up));
+
+        // whatever else the key does this frame is ignored
+        let key = Binding::new(Key::Char('K'));
+        state.handle_input(InputEvent::KeyPressed(key), 0.);
+        state.handle_input(InputEvent::Interact, 0.);
+        state.handle_input(InputEvent::Quit, 0.);
+        assert_eq!(
+            state.settings_menu.draft.input_map.get_bindings(Action::Up),
+            vec![key]
+        );
+        assert_eq!(state.settings_menu.rebinding, None);
+        assert_eq!(state.mode, Mode::Settings);
+        assert!(state.settings_menu.is_restart_required(
+            SettingsOption::Binding(Action::Up),
+            state.get_launch_settings()
+        ));
+
+        state.update(0.);
+        assert!(!state.settings_menu.is_rebinding());
+    }
+
+    #[test]
+    fn a_key_bound_to_another_action_is_not_rebound() {
+        let mut state = start_rebinding(Action::Up);
+        let draft = state.settings_menu.draft.clone();
+
+        state.handle_input(InputEvent::KeyPressed(Binding::new(Key::Char('S'))), 0.);
+        assert_eq!(state.settings_menu.draft, draft);
+        assert!(
+            state
+                .notification
+                .as_deref()
+                .is_some_and(|n| n.contains("down"))
+        );
+    }
+
+    #[test]
+    fn pause_keys_cancel_rebinding() {
+        let mut state = start_rebinding(Action::Up);
+        let draft = state.settings_menu.draft.clone();
+
+        state.handle_input(InputEvent::KeyPressed(Binding::new(Key::Escape)), 0.);
+        state.handle_input(InputEvent::Quit, 0.);
+        assert_eq!(state.settings_menu.draft, draft);
+        assert_eq!(state.settings_menu.rebinding, None);
+        assert_eq!(state.mode, Mode::Settings);
+
+        // a gamepad has no keys, so its back button cancels
+        let mut state = start_rebinding(Action::Up);
+        state.handle_input(InputEvent::Quit, 0.);
+        assert_eq!(state.settings_menu.rebinding, None);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::data::record::{self, DataError};
+use std::fmt;
+
+/// [`XP_CURVE_PATH`] is the data file that configures how much experience each
+/// level takes.
+pub const XP_CURVE_PATH: &str = "assets/xp_curve.txt";
+
+const BASE_HEALTH: u32 = 50;
+const HEALTH_PER_VITALITY: u32 = 10;
+const BASE_STAMINA: u32 = 50;
+const STAMINA_PER_ENDURANCE: u32 = 10;
+const BASE_MOVEMENT_SPEED: f32 = 3.5;
+const SPEED_PER_AGILITY: f32 = 0.1;
+const STARTING_ATTRIBUTE: u32 = 5;
+
+/// [`XpCurve`] decides how much experience is needed to reach each level.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct XpCurve {
+    /// [`XpCurve::base`] is the experience needed to go from level 1 to level 2.
+    pub base: u32,
+    /// [`XpCurve::growth`] is how many times more experience each level needs than
+    /// the one before it.
+    pub growth: f32,
+    /// [`XpCurve::max_level`] is the highest level that can be reached.
+    pub max_level: u32,
+}
+
+impl XpCurve {
+    /// [`XpCurve::load`] reads the first `[xp_curve id]` record of the data file at
+    /// `path`, where any missing field keeps its default.
+    pub fn load(path: &str) -> Result<Self, DataError> {
+        let records = record::read_records(path)?;
+        let Some(curve_record) = records.iter().find(|r| r.kind == "xp_curve") else {
+            return Ok(XpCurve::default());
+        };
+        let default = XpCurve::default();
+        let curve = XpCurve {
+            base: curve_record.parse("base", default.base)?,
+            growth: curve_record.parse("growth", default.growth)?,
+            max_level: curve_record.parse("max_level", default.max_level)?,
+        };
+        let invalid_field = |field: &str, value: String| DataError::InvalidField {
+            line: curve_record.get_line(field),
+            field: field.to_owned(),
+            value,
+        };
+        if curve.base == 0 {
+            return Err(invalid_field("base", curve.base.to_string()));
+        }
+        if curve.growth < 1. {
+            return Err(invalid_field("growth", curve.growth.to_string()));
+        }
+        if curve.max_level == 0 {
+            return Err(invalid_field("max_level", curve.max_level.to_string()));
+        }
+        Ok(curve)
+    }
+
+    /// [`XpCurve::get_xp_to_next_level`] returns the experience needed to go from
+    /// `level` to the level after it.
+    pub fn get_xp_to_next_level(&self, level: u32) -> u32 {
+        (self.base as f32 * self.growth.powi(level.saturating_sub(1) as i32)).round() as u32
+    }
+}
+
+impl Default for XpCurve {
+    fn default() -> Self {
+        Self {
+            base: 100,
+            growth: 1.5,
+            max_level: 30,
+        }
+    }
+}
+
+/// [`Attributes`] are the core values that every other stat is derived from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Attributes {
+    /// [`Attributes::strength`] increases damage.
+    pub strength: u32,
+    /// [`Attributes::agility`] increases movement speed.
+    pub agility: u32,
+    /// [`Attributes::vitality`] increases maximum health.
+    pub vitality: u32,
+    /// [`Attributes::endurance`] increases maximum stamina.
+    pub endurance: u32,
+}
+
+impl Default for Attributes {
+    fn default() -> Self {
+        Self {
+            strength: STARTING_ATTRIBUTE,
+            agility: STARTING_ATTRIBUTE,
+            vitality: STARTING_ATTRIBUTE,
+            endurance: STARTING_ATTRIBUTE,
+        }
+    }
+}
+
+/// [`LevelUp`] is raised for every level gained by [`Stats::gain_xp`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LevelUp {
+    /// [`LevelUp::level`] is the level that was reached.
+    pub level: u32,
+}
+
+impl fmt::Display for LevelUp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Reached level {}!", self.level)
+    }
+}
+
+/// [`Stats`] are the health, stamina, level, experience and [`Attributes`] of a
+/// character.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    /// [`Stats::level`] is the current level, starting at 1.
+    pub level: u32,
+    /// [`Stats::xp`] is the experience gained towards the next level.
+    pub xp: u32,
+    /// [`Stats::health`] is the current health, up to [`Stats::get_max_health`].
+    pub health: u32,
+    /// [`Stats::stamina`] is the current stamina, up to [`Stats::get_max_stamina`].
+    pub stamina: u32,
+    /// [`Stats::attributes`] are the [`Attributes`] the other stats come from.
+    pub attributes: Attributes,
+}
+
+impl Stats {
+    /// [`Stats::get_max_health`] returns the most health allowed by
+    /// [`Attributes::vitality`].
+    pub fn get_max_health(&self) -> u32 {
+        BASE_HEALTH + self.attributes.vitality * HEALTH_PER_VITALITY
+    }
+
+    /// [`Stats::get_max_stamina`] returns the most stamina allowed by
+    /// [`Attributes::endurance`].
+    pub fn get_max_stamina(&self) -> u32 {
+        BASE_STAMINA + self.attributes.endurance * STAMINA_PER_ENDURANCE
+    }
+
+    /// [`Stats::get_speed`] returns the movement speed in tiles per second allowed
+    /// by [`Attributes::agility`].
+    pub fn get_speed(&self) -> f32 {
+        BASE_MOVEMENT_SPEED + self.attributes.agility as f32 * SPEED_PER_AGILITY
+    }
+
+    /// [`Stats::gain_xp`] adds an `amount` of experience, levelling up as many
+    /// times as the [`XpCurve`] allows. Every level gained raises each attribute by
+    /// one and restores health and stamina, and is returned as a [`LevelUp`].
+    pub fn gain_xp(&mut self, amount: u32, curve: &XpCurve) -> Vec<LevelUp> {
+        let mut level_ups = Vec::new();
+        if self.level >= curve.max_level {
+            return level_ups;
+        }
+        self.xp = self.xp.saturating_add(amount);
+        while self.level < curve.max_level && self.xp >= curve.get_xp_to_next_level(self.level) {
+            self.xp -= curve.get_xp_to_next_level(self.level);
+            self.level += 1;
+            self.attributes.strength += 1;
+            self.attributes.agility += 1;
+            self.attributes.vitality += 1;
+            self.attributes.endurance += 1;
+            self.health = self.get_max_health();
+            self.stamina = self.get_max_stamina();
+            level_ups.push(LevelUp { level: self.level });
+        }
+        if self.level >= curve.max_level {
+            self.xp = 0;
+        }
+        level_ups
+    }
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        let mut stats = Self {
+            level: 1,
+            xp: 0,
+            health: 0,
+            stamina: 0,
+            attributes: Attributes::default(),
+        };
+        stats.health = stats.get_max_health();
+        stats.stamina = stats.get_max_stamina();
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_xp_curve_reports_the_field_and_its_line() {
+        let path = std::env::temp_dir().join("rust_and_ruin_xp_curve.txt");
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "[xp_curve default]\nbase = 100\ngrowth = 0.5\n").unwrap();
+        let result = XpCurve::load(path);
+        std::fs::remove_file(path).unwrap();
+        match result {
+            Err(DataError::InvalidField { line, field, value }) => {
+                assert_eq!((line, field.as_str(), value.as_str()), (3, "growth", "0.5"));
+            }
+            other => panic!("expected an invalid growth, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+/// [`TitleOption`] is an entry of the title screen menu.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TitleOption {
+    /// [`TitleOption::NewGame`] starts over with fresh assets.
+    NewGame,
+    /// [`TitleOption::Continue`] loads the save, and is only enabled if there is one.
+    Continue,
+    /// [`TitleOption::Settings`] opens the settings screen.
+    Settings,
+    /// [`TitleOption::Credits`] shows who made the game.
+    Credits,
+    /// [`TitleOption::Quit`] closes the game.
+    Quit,
+}
+
+impl TitleOption {
+    /// [`TitleOption::ALL`] is every [`TitleOption`] in the order they are shown.
+    pub const ALL: [TitleOption; 5] = [
+        TitleOption::NewGame,
+        TitleOption::Continue,
+        TitleOption::Settings,
+        TitleOption::Credits,
+        TitleOption::Quit,
+    ];
+
+    /// [`TitleOption::is_enabled`] returns `true` if a [`TitleOption`] can be chosen,
+    /// depending on whether there `has_save`.
+    pub fn is_enabled(&self, has_save: bool) -> bool {
+        match self {
+            TitleOption::Continue => has_save,
+            _ => true,
+        }
+    }
+}
+
+impl std::fmt::Display for TitleOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TitleOption::NewGame => write!(f, "New Game"),
+            TitleOption::Continue => write!(f, "Continue"),
+            TitleOption::Settings => write!(f, "Settings"),
+            TitleOption::Credits => write!(f, "Credits"),
+            TitleOption::Quit => write!(f, "Quit"),
+        }
+    }
+}
+
+/// [`TitleMenu`] holds the state of the title screen menu.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TitleMenu {
+    /// [`TitleMenu::selected`] is the index of the selected [`TitleOption`].
+    pub selected: usize,
+    /// [`TitleMenu::is_showing_credits`] is `true` while the credits are shown in
+    /// place of the menu.
+    pub is_showing_credits: bool,
+}
+
+impl TitleMenu {
+    /// [`TitleMenu::move_cursor`] moves the selection by `y` rows, skipping over any
+    /// [`TitleOption`] that isn't enabled with or without a save, and staying put if
+    /// there is nothing enabled that way.
+    pub fn move_cursor(&mut self, y: i32, has_save: bool) {
+        let options = 0..TitleOption::ALL.len() as i32;
+        for _ in 0..y.unsigned_abs() {
+            let mut next = self.selected as i32 + y.signum();
+            while options.contains(&next) && !TitleOption::ALL[next as usize].is_enabled(has_save) {
+                next += y.signum();
+            }
+            if !options.contains(&next) {
+                break;
+            }
+            self.selected = next as usize;
+        }
+    }
+
+    /// [`TitleMenu::select`] selects the [`TitleOption`] at `index`, and returns `true`
+    /// if it is enabled with or without a save, leaving the selection alone otherwise.
+    pub fn select(&mut self, index: usize, has_save: bool) -> bool {
+        let is_enabled = TitleOption::ALL
+            .get(index)
+            .is_some_and(|option| option.is_enabled(has_save));
+        if is_enabled {
+            self.selected = index;
+        }
+        is_enabled
+    }
+
+    /// [`TitleMenu::get_selected`] returns the selected [`TitleOption`].
+    pub fn get_selected(&self) -> TitleOption {
+        TitleOption::ALL[self.selected.min(TitleOption::ALL.len() - 1)]
+    }
+}
@@ -0,0 +1,233 @@
+use super::{
+    equipment::EquipSlot,
+    item::{Item, ItemProperty, ItemType},
+};
+use crate::{
+    data::record::{self, DataError, Record},
+    renderer::sprite,
+};
+use std::fmt;
+
+/// [`ITEMS_PATH`] is the data file every [`Item`] is defined in.
+pub const ITEMS_PATH: &str = "assets/items.txt";
+
+/// [`ItemCatalog`] holds the definition of every [`Item`] in the game, by
+/// [`Item::id`].
+#[derive(Clone, Debug, Default)]
+pub struct ItemCatalog {
+    items: Vec<Item>,
+}
+
+impl ItemCatalog {
+    /// [`ItemCatalog::load`] reads and parses the item data file at `path`.
+    pub fn load(path: &str) -> Result<Self, ItemError> {
+        let records = record::read_records(path)?;
+        Self::from_records(&records)
+    }
+
+    /// [`ItemCatalog::parse`] parses the `source` of an item data file, where each
+    /// [`Item`] is an `[item id]` header followed by its fields.
+    pub fn parse(source: &str) -> Result<Self, ItemError> {
+        let records = record::parse_records(source)?;
+        Self::from_records(&records)
+    }
+
+    fn from_records(records: &[Record]) -> Result<Self, ItemError> {
+        let mut catalog = ItemCatalog::default();
+        for item_record in records.iter().filter(|r| r.kind == "item") {
+            if catalog.get(&item_record.id).is_some() {
+                return Err(ItemError::DuplicateId {
+                    id: item_record.id.clone(),
+                    line: item_record.line,
+                });
+            }
+            let item = parse_item(item_record)?;
+            if !sprite::is_known_sprite(&item.sprite_id) {
+                return Err(ItemError::UnknownSpriteId {
+                    id: item.id,
+                    sprite_id: item.sprite_id,
+                    line: item_record.line,
+                });
+            }
+            if let Some(overlay_sprite_id) = &item.overlay_sprite_id
+                && !sprite::is_known_sprite(overlay_sprite_id)
+            {
+                return Err(ItemError::UnknownSpriteId {
+                    id: item.id,
+                    sprite_id: overlay_sprite_id.clone(),
+                    line: item_record.line,
+                });
+            }
+            catalog.items.push(item);
+        }
+        Ok(catalog)
+    }
+
+    /// [`ItemCatalog::get`] returns [`Some`] [`Item`] defined with `id`, and
+    /// [`None`] otherwise.
+    pub fn get(&self, id: &str) -> Option<&Item> {
+        self.items.iter().find(|i| i.id == id)
+    }
+
+    /// [`ItemCatalog::items`] returns every [`Item`] in the order they were defined.
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+}
+
+fn parse_item(item_record: &Record) -> Result<Item, DataError> {
+    let mut properties = Vec::new();
+    if let Some(damage) = item_record.parse_optional("damage")? {
+        properties.push(ItemProperty::Damage(damage));
+    }
+    if let Some(armor) = item_record.parse_optional("armor_rating")? {
+        properties.push(ItemProperty::ArmorRating(armor));
+    }
+    if let Some(healing) = item_record.parse_optional("healing")? {
+        properties.push(ItemProperty::Healing(healing));
+    }
+
+    // weapons are always wielded, and armor has to say where it is worn
+    let item_type: ItemType = item_record.parse_required("type")?;
+    let slot = match item_type {
+        ItemType::Weapon => Some(EquipSlot::Weapon),
+        ItemType::Armor => Some(item_record.parse_required("slot")?),
+        ItemType::Material | ItemType::Consumable => None,
+    };
+
+    Ok(Item {
+        id: item_record.id.clone(),
+        name: item_record.require("name")?.to_owned(),
+        description: item_record
+            .get("description")
+            .unwrap_or_default()
+            .to_owned(),
+        sprite_id: item_record.require("sprite")?.to_owned(),
+        item_type,
+        max_stack: item_record.parse("max_stack", 1)?.max(1),
+        weight: item_record.parse("weight", 0.)?,
+        value: item_record.parse("value", 0)?,
+        rarity: item_record.parse("rarity", Default::default())?,
+        properties,
+        slot,
+        overlay_sprite_id: item_record.get("overlay").map(str::to_owned),
+    })
+}
+
+/// [`ItemError`] represents a problem loading an [`ItemCatalog`].
+#[derive(Debug)]
+pub enum ItemError {
+    /// [`ItemError::Data`] means the item data file couldn't be read or parsed.
+    Data(DataError),
+    /// [`ItemError::DuplicateId`] means two [`Item`] were defined with the same `id`.
+    DuplicateId { id: String, line: usize },
+    /// [`ItemError::UnknownSpriteId`] means an [`Item`] uses a sprite that doesn't exist.
+    UnknownSpriteId {
+        id: String,
+        sprite_id: String,
+        line: usize,
+    },
+}
+
+impl From<DataError> for ItemError {
+    fn from(error: DataError) -> Self {
+        ItemError::Data(error)
+    }
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemError::Data(error) => write!(f, "item data error: {error}"),
+            ItemError::DuplicateId { id, line } => {
+                write!(f, "line {line}: item `{id}` is defined more than once")
+            }
+            ItemError::UnknownSpriteId {
+                id,
+                sprite_id,
+                line,
+            } => write!(
+                f,
+                "line {line}: item `{id}` uses unknown sprite `{sprite_id}`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ItemError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: &str = "\
+[item rusty_sword]
+name = Rusty Sword
+sprite = icon_sword
+type = weapon
+damage = 3
+
+[item leather_cap]
+name = Leather Cap
+sprite = icon_helmet
+type = armor
+slot = head
+armor_rating = 1
+max_stack = 0
+";
+
+    #[test]
+    fn items_are_parsed_with_their_properties() {
+        let catalog = ItemCatalog::parse(ITEMS).unwrap();
+        assert_eq!(catalog.items().len(), 2);
+
+        let sword = catalog.get("rusty_sword").unwrap();
+        assert_eq!(sword.name, "Rusty Sword");
+        assert_eq!(sword.slot, Some(EquipSlot::Weapon));
+        assert_eq!(sword.properties, vec![ItemProperty::Damage(3)]);
+
+        // a stack always holds at least one item
+        let cap = catalog.get("leather_cap").unwrap();
+        assert_eq!(cap.slot, Some(EquipSlot::Head));
+        assert_eq!(cap.max_stack, 1);
+        assert!(catalog.get("iron_sword").is_none());
+    }
+
+    #[test]
+    fn duplicate_ids_are_rejected() {
+        let source = format!(
+            "{ITEMS}\n[item rusty_sword]\nname = Again\nsprite = icon_sword\ntype = weapon\n"
+        );
+        match ItemCatalog::parse(&source) {
+            Err(ItemError::DuplicateId { id, line }) => {
+                assert_eq!(id, "rusty_sword");
+                assert_eq!(line, 15);
+            }
+            other => panic!("expected a duplicate id, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_sprites_are_rejected() {
+        let source = "[item stick]\nname = Stick\nsprite = icon_stick\ntype = material\n";
+        assert!(matches!(
+            ItemCatalog::parse(source),
+            Err(ItemError::UnknownSpriteId { sprite_id, line: 1, .. }) if sprite_id == "icon_stick"
+        ));
+
+        let source = "[item cap]\nname = Cap\nsprite = icon_helmet\ntype = armor\nslot = head\noverlay = cap_overlay\n";
+        assert!(matches!(
+            ItemCatalog::parse(source),
+            Err(ItemError::UnknownSpriteId { sprite_id, .. }) if sprite_id == "cap_overlay"
+        ));
+    }
+
+    #[test]
+    fn armor_needs_a_slot() {
+        let source = "[item cap]\nname = Cap\nsprite = icon_helmet\ntype = armor\n";
+        assert!(matches!(
+            ItemCatalog::parse(source),
+            Err(ItemError::Data(DataError::MissingField { field, .. })) if field == "slot"
+        ));
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+
+/// [`Currency`] represents the denominations of coin in the game, from least to
+/// most valuable.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Currency {
+    /// [`Currency::Copper`] is the base denomination every [`Price`] is counted in.
+    Copper,
+    /// [`Currency::Silver`] is worth 100 [`Currency::Copper`].
+    Silver,
+    /// [`Currency::Gold`] is worth 100 [`Currency::Silver`].
+    Gold,
+}
+
+impl Currency {
+    /// [`Currency::get_value`] returns how many [`Currency::Copper`] one coin of a
+    /// [`Currency`] is worth.
+    pub fn get_value(&self) -> u64 {
+        match self {
+            Currency::Copper => 1,
+            Currency::Silver => 100,
+            Currency::Gold => 10_000,
+        }
+    }
+
+    /// [`Currency::get_symbol`] returns the short symbol of a [`Currency`].
+    pub fn get_symbol(&self) -> &str {
+        match self {
+            Currency::Copper => "c",
+            Currency::Silver => "s",
+            Currency::Gold => "g",
+        }
+    }
+}
+
+/// [`Price`] is an amount of money, stored as [`Currency::Copper`], used by item
+/// values, shops and loot alike.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Price {
+    copper: u64,
+}
+
+impl Price {
+    /// [`Price::new`] creates a new [`Price`] of an `amount` of a [`Currency`].
+    pub fn new(amount: u64, currency: Currency) -> Self {
+        Self {
+            copper: amount.saturating_mul(currency.get_value()),
+        }
+    }
+
+    /// [`Price::from_copper`] creates a new [`Price`] of an `amount` of
+    /// [`Currency::Copper`].
+    pub fn from_copper(amount: u64) -> Self {
+        Price::new(amount, Currency::Copper)
+    }
+
+    /// [`Price::get_copper`] returns the total of a [`Price`] in [`Currency::Copper`].
+    pub fn get_copper(&self) -> u64 {
+        self.copper
+    }
+
+    /// [`Price::fraction`] returns a `fraction` of a [`Price`], rounded down.
+    pub fn fraction(&self, fraction: f32) -> Self {
+        Self {
+            copper: (self.copper as f64 * fraction.clamp(0., 1.) as f64) as u64,
+        }
+    }
+}
+
+// show the largest denominations first, i.e.: `1g 20s 5c`
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut remaining = self.copper;
+        let mut parts: Vec<String> = Vec::new();
+        for currency in [Currency::Gold, Currency::Silver, Currency::Copper] {
+            let amount = remaining / currency.get_value();
+            remaining %= currency.get_value();
+            if amount > 0 {
+                parts.push(format!("{amount}{}", currency.get_symbol()));
+            }
+        }
+        if parts.is_empty() {
+            write!(f, "0{}", Currency::Copper.get_symbol())
+        } else {
+            write!(f, "{}", parts.join(" "))
+        }
+    }
+}
+
+/// [`Wallet`] holds the money of the [`Player`](crate::game::player::Player), and
+/// never lets its balance go negative.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Wallet {
+    balance: Price,
+}
+
+impl Wallet {
+    /// [`Wallet::new`] creates a new [`Wallet`] holding a `balance`.
+    pub fn new(balance: Price) -> Self {
+        Self { balance }
+    }
+
+    /// [`Wallet::get_balance`] returns the [`Price`] a [`Wallet`] holds.
+    pub fn get_balance(&self) -> Price {
+        self.balance
+    }
+
+    /// [`Wallet::can_afford`] returns `true` if a [`Wallet`] holds at least a `price`.
+    pub fn can_afford(&self, price: Price) -> bool {
+        self.balance.copper >= price.copper
+    }
+
+    /// [`Wallet::add`] will attempt to add an `amount` to a [`Wallet`], and return a
+    /// [`WalletError`] if the balance would overflow.
+    pub fn add(&mut self, amount: Price) -> Result<(), WalletError> {
+        self.balance.copper = self
+            .balance
+            .copper
+            .checked_add(amount.copper)
+            .ok_or(WalletError::Overflow)?;
+        Ok(())
+    }
+
+    /// [`Wallet::spend`] will attempt to take a `price` out of a [`Wallet`], and
+    /// return a [`WalletError`] without spending anything if it can't afford it.
+    pub fn spend(&mut self, price: Price) -> Result<(), WalletError> {
+        self.balance.copper = self.balance.copper.checked_sub(price.copper).ok_or(
+            WalletError::InsufficientFunds {
+                price,
+                balance: self.balance,
+            },
+        )?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Wallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.balance)
+    }
+}
+
+/// [`WalletError`] represents the reason money couldn't be added to or spent from
+/// a [`Wallet`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WalletError {
+    /// [`WalletError::InsufficientFunds`] means the `balance` is less than the `price`.
+    InsufficientFunds { price: Price, balance: Price },
+    /// [`WalletError::Overflow`] means the balance can't hold any more money.
+    Overflow,
+}
+
+impl fmt::Display for WalletError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WalletError::InsufficientFunds { price, balance } => {
+                write!(f, "Not enough money: costs {price}, you have {balance}")
+            }
+            WalletError::Overflow => write!(f, "Your wallet can't hold any more money"),
+        }
+    }
+}
+
+impl std::error::Error for WalletError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prices_are_shown_largest_denomination_first() {
+        assert_eq!(Price::from_copper(12_005).to_string(), "1g 20s 5c");
+        assert_eq!(Price::new(3, Currency::Silver).to_string(), "3s");
+        assert_eq!(Price::default().to_string(), "0c");
+    }
+
+    #[test]
+    fn fractions_round_down_and_stay_within_the_price() {
+        let price = Price::from_copper(99);
+        assert_eq!(price.fraction(0.5), Price::from_copper(49));
+        assert_eq!(price.fraction(2.), price);
+        assert_eq!(price.fraction(-1.), Price::default());
+    }
+
+    #[test]
+    fn adding_past_the_largest_balance_overflows() {
+        let mut wallet = Wallet::new(Price::from_copper(u64::MAX - 1));
+        assert_eq!(wallet.add(Price::from_copper(1)), Ok(()));
+        assert_eq!(
+            wallet.add(Price::from_copper(1)),
+            Err(WalletError::Overflow)
+        );
+        assert_eq!(wallet.get_balance(), Price::from_copper(u64::MAX));
+    }
+
+    #[test]
+    fn spending_more_than_the_balance_spends_nothing() {
+        let mut wallet = Wallet::new(Price::from_copper(50));
+        assert!(wallet.can_afford(Price::from_copper(50)));
+        assert!(!wallet.can_afford(Price::from_copper(51)));
+
+        assert_eq!(
+            wallet.spend(Price::from_copper(51)),
+            Err(WalletError::InsufficientFunds {
+                price: Price::from_copper(51),
+                balance: Price::from_copper(50),
+            })
+        );
+        assert_eq!(wallet.get_balance(), Price::from_copper(50));
+
+        assert_eq!(wallet.spend(Price::from_copper(50)), Ok(()));
+        assert_eq!(wallet.get_balance(), Price::default());
+    }
+}
@@ -0,0 +1,134 @@
+use super::item::Item;
+use std::{fmt, str::FromStr};
+
+/// [`EquipSlot`] represents where an [`Item`] is worn or wielded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EquipSlot {
+    /// [`EquipSlot::Head`] is for helmets and hats.
+    Head,
+    /// [`EquipSlot::Body`] is for tunics and chest armor.
+    Body,
+    /// [`EquipSlot::Hands`] is for gloves.
+    Hands,
+    /// [`EquipSlot::Feet`] is for boots.
+    Feet,
+    /// [`EquipSlot::Weapon`] is for whatever is held to fight with.
+    Weapon,
+}
+
+impl EquipSlot {
+    /// [`EquipSlot::ALL`] is every [`EquipSlot`], in the order they are shown.
+    pub const ALL: [EquipSlot; 5] = [
+        EquipSlot::Head,
+        EquipSlot::Body,
+        EquipSlot::Hands,
+        EquipSlot::Feet,
+        EquipSlot::Weapon,
+    ];
+
+    fn get_index(&self) -> usize {
+        match self {
+            EquipSlot::Head => 0,
+            EquipSlot::Body => 1,
+            EquipSlot::Hands => 2,
+            EquipSlot::Feet => 3,
+            EquipSlot::Weapon => 4,
+        }
+    }
+}
+
+impl FromStr for EquipSlot {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(EquipSlot::Head),
+            "body" => Ok(EquipSlot::Body),
+            "hands" => Ok(EquipSlot::Hands),
+            "feet" => Ok(EquipSlot::Feet),
+            "weapon" => Ok(EquipSlot::Weapon),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for EquipSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquipSlot::Head => write!(f, "Head"),
+            EquipSlot::Body => write!(f, "Body"),
+            EquipSlot::Hands => write!(f, "Hands"),
+            EquipSlot::Feet => write!(f, "Feet"),
+            EquipSlot::Weapon => write!(f, "Weapon"),
+        }
+    }
+}
+
+/// [`Equipment`] holds at most one [`Item`] in every [`EquipSlot`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Equipment {
+    items: [Option<Item>; 5],
+}
+
+impl Equipment {
+    /// [`Equipment::get`] returns [`Some`] [`Item`] in a `slot`, and [`None`] if
+    /// it is empty.
+    pub fn get(&self, slot: EquipSlot) -> Option<&Item> {
+        self.items[slot.get_index()].as_ref()
+    }
+
+    /// [`Equipment::equip`] puts an `item` in its [`EquipSlot`], and returns
+    /// [`Some`] [`Item`] that was there before. A [`EquipError::NotEquippable`]
+    /// is returned if the `item` has no [`EquipSlot`].
+    pub fn equip(&mut self, item: Item) -> Result<Option<Item>, EquipError> {
+        let slot = item.slot.ok_or(EquipError::NotEquippable)?;
+        Ok(self.items[slot.get_index()].replace(item))
+    }
+
+    /// [`Equipment::unequip`] empties a `slot`, and returns [`Some`] [`Item`] that
+    /// was in it.
+    pub fn unequip(&mut self, slot: EquipSlot) -> Option<Item> {
+        self.items[slot.get_index()].take()
+    }
+
+    /// [`Equipment::items`] returns every equipped [`Item`], in [`EquipSlot::ALL`]
+    /// order.
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter().flatten()
+    }
+
+    /// [`Equipment::get_damage`] returns the total damage of every equipped [`Item`].
+    pub fn get_damage(&self) -> u32 {
+        self.items().map(|item| item.get_damage()).sum()
+    }
+
+    /// [`Equipment::get_armor_rating`] returns the total armor rating of every
+    /// equipped [`Item`].
+    pub fn get_armor_rating(&self) -> u32 {
+        self.items().map(|item| item.get_armor_rating()).sum()
+    }
+}
+
+/// [`EquipError`] represents why an [`Item`] couldn't be equipped or unequipped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EquipError {
+    /// [`EquipError::NotEquippable`] means the [`Item`] can't be worn or wielded.
+    NotEquippable,
+    /// [`EquipError::EmptySlot`] means there is nothing in the [`EquipSlot`].
+    EmptySlot(EquipSlot),
+    /// [`EquipError::InventoryFull`] means there is no room in the inventory for
+    /// the [`Item`] being taken off.
+    InventoryFull,
+}
+
+impl fmt::Display for EquipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquipError::NotEquippable => write!(f, "That can't be equipped"),
+            EquipError::EmptySlot(slot) => write!(f, "Nothing is equipped on {slot}"),
+            EquipError::InventoryFull => write!(f, "Not enough inventory space"),
+        }
+    }
+}
+
+impl std::error::Error for EquipError {}
@@ -0,0 +1,139 @@
+use super::item::Item;
+
+/// [`DEFAULT_INVENTORY_SLOTS`] is the amount of [`ItemStack`] an [`Inventory`] holds by default.
+pub const DEFAULT_INVENTORY_SLOTS: usize = 20;
+
+/// [`ItemStack`] is an amount of the same [`Item`] held in one slot of an [`Inventory`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemStack {
+    /// [`ItemStack::item`] is the [`Item`] held in an [`ItemStack`].
+    pub item: Item,
+    /// [`ItemStack::quantity`] is how many of [`ItemStack::item`] are held.
+    pub quantity: u32,
+}
+
+/// [`Inventory`] holds a limited amount of [`ItemStack`], and keeps track of which
+/// slot is selected.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Inventory {
+    stacks: Vec<ItemStack>,
+    capacity: usize,
+    /// [`Inventory::selected`] is the index of the currently selected slot.
+    pub selected: usize,
+}
+
+impl Inventory {
+    /// [`Inventory::new`] creates a new empty [`Inventory`] with `capacity` slots.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            stacks: Vec::new(),
+            capacity,
+            selected: 0,
+        }
+    }
+
+    /// [`Inventory::capacity`] returns the amount of slots of an [`Inventory`].
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// [`Inventory::stacks`] returns every [`ItemStack`] of an [`Inventory`], in slot order.
+    pub fn stacks(&self) -> &[ItemStack] {
+        &self.stacks
+    }
+
+    /// [`Inventory::add_item`] will attempt to add a `quantity` of an `item` to an
+    /// [`Inventory`], filling stacks of the same [`Item`] up to [`Item::max_stack`]
+    /// before using empty slots, and return the result of the attempt. Nothing is
+    /// added if there isn't room for all of it.
+    pub fn add_item(&mut self, item: &Item, quantity: u32) -> bool {
+        if self.get_space_for(item) < quantity {
+            return false;
+        }
+        let mut remaining = quantity;
+        for stack in self.stacks.iter_mut().filter(|s| s.item.id == item.id) {
+            let added = remaining.min(item.max_stack.saturating_sub(stack.quantity));
+            stack.quantity += added;
+            remaining -= added;
+        }
+        while remaining > 0 {
+            let added = remaining.min(item.max_stack);
+            self.stacks.push(ItemStack {
+                item: item.clone(),
+                quantity: added,
+            });
+            remaining -= added;
+        }
+        true
+    }
+
+    /// [`Inventory::remove_item`] will attempt to remove a `quantity` of the [`Item`]
+    /// with an [`Item::id`] of `id` from an [`Inventory`], and return the result of
+    /// the attempt. Nothing is removed if there isn't enough of the [`Item`].
+    pub fn remove_item(&mut self, id: &str, quantity: u32) -> bool {
+        if self.count(id) < quantity {
+            return false;
+        }
+        let mut remaining = quantity;
+        // take from the last stacks first, so full stacks stay in place
+        for stack in self.stacks.iter_mut().rev().filter(|s| s.item.id == id) {
+            let removed = remaining.min(stack.quantity);
+            stack.quantity -= removed;
+            remaining -= removed;
+        }
+        self.stacks.retain(|s| s.quantity > 0);
+        true
+    }
+
+    /// [`Inventory::count`] returns how many of the [`Item`] with an [`Item::id`]
+    /// of `id` are held.
+    pub fn count(&self, id: &str) -> u32 {
+        self.stacks
+            .iter()
+            .filter(|s| s.item.id == id)
+            .map(|s| s.quantity)
+            .sum()
+    }
+
+    /// [`Inventory::get_space_for`] returns how many more of an `item` fit into
+    /// an [`Inventory`].
+    pub fn get_space_for(&self, item: &Item) -> u32 {
+        let space_in_stacks: u32 = self
+            .stacks
+            .iter()
+            .filter(|s| s.item.id == item.id)
+            .map(|s| item.max_stack.saturating_sub(s.quantity))
+            .sum();
+        let empty_slots = self.capacity.saturating_sub(self.stacks.len()) as u32;
+        space_in_stacks + empty_slots * item.max_stack
+    }
+
+    /// [`Inventory::get_selected_stack`] returns [`Some`] [`ItemStack`] in the selected
+    /// slot, and [`None`] if the slot is empty.
+    pub fn get_selected_stack(&self) -> Option<&ItemStack> {
+        self.stacks.get(self.selected)
+    }
+
+    /// [`Inventory::select`] selects the slot at `index`, if it exists.
+    pub fn select(&mut self, index: usize) {
+        if index < self.capacity {
+            self.selected = index;
+        }
+    }
+
+    /// [`Inventory::move_cursor`] moves the selected slot by `x` columns and `y` rows
+    /// of a grid `columns` wide, staying within the [`Inventory`].
+    pub fn move_cursor(&mut self, x: i32, y: i32, columns: usize) {
+        let columns = columns.max(1) as i32;
+        let rows = self.capacity.div_ceil(columns as usize) as i32;
+        let column = (self.selected as i32 % columns + x).clamp(0, columns - 1);
+        let row = (self.selected as i32 / columns + y).clamp(0, rows - 1);
+        self.select((row * columns + column) as usize);
+    }
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Inventory::new(DEFAULT_INVENTORY_SLOTS)
+    }
+}
@@ -1,8 +1,186 @@
+use super::{currency::Price, equipment::EquipSlot};
+use crate::renderer::color::Color;
+use std::str::FromStr;
+
+/// [`ItemType`] represents the different kinds of [`Item`] in the game.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ItemType {
+    /// [`ItemType::Weapon`] represents an [`Item`] that can be wielded.
     Weapon,
+    /// [`ItemType::Armor`] represents an [`Item`] that can be worn.
     Armor,
+    /// [`ItemType::Material`] represents an [`Item`] used to craft other items.
+    Material,
+    /// [`ItemType::Consumable`] represents an [`Item`] that is used up.
+    Consumable,
+}
+
+impl FromStr for ItemType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "weapon" => Ok(ItemType::Weapon),
+            "armor" => Ok(ItemType::Armor),
+            "material" => Ok(ItemType::Material),
+            "consumable" => Ok(ItemType::Consumable),
+            _ => Err(()),
+        }
+    }
+}
+
+/// [`Rarity`] represents how hard an [`Item`] is to come by.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+impl Rarity {
+    /// [`Rarity::get_color`] gets the [`Color`] an [`Item`] name is drawn with.
+    pub fn get_color(&self) -> Color {
+        match self {
+            Rarity::Common => Color::White,
+            Rarity::Uncommon => Color::Green,
+            Rarity::Rare => Color::Cyan,
+            Rarity::Epic => Color::Magenta,
+            Rarity::Legendary => Color::Yellow,
+        }
+    }
+}
+
+impl FromStr for Rarity {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "common" => Ok(Rarity::Common),
+            "uncommon" => Ok(Rarity::Uncommon),
+            "rare" => Ok(Rarity::Rare),
+            "epic" => Ok(Rarity::Epic),
+            "legendary" => Ok(Rarity::Legendary),
+            _ => Err(()),
+        }
+    }
 }
 
+impl std::fmt::Display for Rarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rarity::Common => write!(f, "Common"),
+            Rarity::Uncommon => write!(f, "Uncommon"),
+            Rarity::Rare => write!(f, "Rare"),
+            Rarity::Epic => write!(f, "Epic"),
+            Rarity::Legendary => write!(f, "Legendary"),
+        }
+    }
+}
+
+/// [`ItemProperty`] represents a typed property that only some [`Item`] have.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ItemProperty {
+    /// [`ItemProperty::Damage`] is how much damage an [`Item`] deals.
+    Damage(u32),
+    /// [`ItemProperty::ArmorRating`] is how much damage an [`Item`] prevents.
+    ArmorRating(u32),
+    /// [`ItemProperty::Healing`] is how much health an [`Item`] restores.
+    Healing(u32),
+}
+
+impl std::fmt::Display for ItemProperty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemProperty::Damage(damage) => write!(f, "Damage {damage}"),
+            ItemProperty::ArmorRating(armor) => write!(f, "Armor {armor}"),
+            ItemProperty::Healing(healing) => write!(f, "Heals {healing}"),
+        }
+    }
+}
+
+/// [`Item`] represents something the [`Player`](crate::game::player::Player) can
+/// carry. Every [`Item`] is defined in a data file and loaded into an
+/// [`ItemCatalog`](super::catalog::ItemCatalog).
+#[derive(Clone, Debug, PartialEq)]
 pub struct Item {
+    /// [`Item::id`] is the stable identifier of an [`Item`], used for stacking,
+    /// recipes and save files.
+    pub id: String,
+    /// [`Item::name`] is the display name of an [`Item`].
+    pub name: String,
+    /// [`Item::description`] is the flavor text of an [`Item`].
+    pub description: String,
+    /// [`Item::sprite_id`] is the id of the sprite an [`Item`] is drawn with.
+    pub sprite_id: String,
+    /// [`Item::item_type`] is the [`ItemType`] of an [`Item`].
     pub item_type: ItemType,
+    /// [`Item::max_stack`] is how many of an [`Item`] fit into one inventory slot.
+    pub max_stack: u32,
+    /// [`Item::weight`] is how heavy one of an [`Item`] is.
+    pub weight: f32,
+    /// [`Item::value`] is how much one of an [`Item`] is worth, in copper.
+    pub value: u32,
+    /// [`Item::rarity`] is the [`Rarity`] of an [`Item`].
+    pub rarity: Rarity,
+    /// [`Item::properties`] is every [`ItemProperty`] of an [`Item`].
+    pub properties: Vec<ItemProperty>,
+    /// [`Item::slot`] is [`Some`] [`EquipSlot`] an [`Item`] is equipped in, or
+    /// [`None`] if it can't be equipped.
+    pub slot: Option<EquipSlot>,
+    /// [`Item::overlay_sprite_id`] is [`Some`] sprite id drawn over the player
+    /// while an [`Item`] is equipped, and [`None`] to draw nothing.
+    pub overlay_sprite_id: Option<String>,
+}
+
+impl Item {
+    /// [`Item::new`] creates a new [`Item`] that stacks by itself and has no
+    /// [`Item::properties`].
+    pub fn new(id: &str, name: &str, item_type: ItemType) -> Self {
+        Self {
+            id: id.to_owned(),
+            name: name.to_owned(),
+            description: String::new(),
+            sprite_id: "unknown".to_owned(),
+            item_type,
+            max_stack: 1,
+            weight: 0.,
+            value: 0,
+            rarity: Rarity::default(),
+            properties: Vec::new(),
+            slot: None,
+            overlay_sprite_id: None,
+        }
+    }
+
+    /// [`Item::get_price`] returns the [`Price`] of one of an [`Item`], based on
+    /// its [`Item::value`] in copper.
+    pub fn get_price(&self) -> Price {
+        Price::from_copper(self.value as u64)
+    }
+
+    /// [`Item::get_damage`] returns the total [`ItemProperty::Damage`] of an [`Item`].
+    pub fn get_damage(&self) -> u32 {
+        self.properties
+            .iter()
+            .map(|p| match p {
+                ItemProperty::Damage(damage) => *damage,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// [`Item::get_armor_rating`] returns the total [`ItemProperty::ArmorRating`]
+    /// of an [`Item`].
+    pub fn get_armor_rating(&self) -> u32 {
+        self.properties
+            .iter()
+            .map(|p| match p {
+                ItemProperty::ArmorRating(armor) => *armor,
+                _ => 0,
+            })
+            .sum()
+    }
 }
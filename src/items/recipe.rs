@@ -0,0 +1,430 @@
+use super::{catalog::ItemCatalog, inventory::Inventory, item::Item};
+use crate::{
+    data::record::{self, DataError, Record},
+    maps::building_type::BuildingType,
+};
+use std::fmt;
+
+/// [`RECIPES_PATH`] is the data file every [`Recipe`] is defined in.
+pub const RECIPES_PATH: &str = "assets/recipes.txt";
+
+/// [`RecipeItem`] is a quantity of an [`Item`] used or made by a [`Recipe`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecipeItem {
+    /// [`RecipeItem::item_id`] is the [`Item::id`] of a [`RecipeItem`].
+    pub item_id: String,
+    /// [`RecipeItem::quantity`] is how many of the [`Item`] are needed or made.
+    pub quantity: u32,
+}
+
+/// [`Recipe`] turns a set of input [`Item`] into a set of output [`Item`], optionally
+/// only at a certain [`BuildingType`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recipe {
+    /// [`Recipe::id`] is the stable identifier of a [`Recipe`].
+    pub id: String,
+    /// [`Recipe::name`] is the display name of a [`Recipe`].
+    pub name: String,
+    /// [`Recipe::inputs`] are the [`RecipeItem`] consumed by crafting a [`Recipe`].
+    pub inputs: Vec<RecipeItem>,
+    /// [`Recipe::outputs`] are the [`RecipeItem`] made by crafting a [`Recipe`].
+    pub outputs: Vec<RecipeItem>,
+    /// [`Recipe::building`] is [`Some`] [`BuildingType`] a [`Recipe`] must be crafted
+    /// at, or [`None`] if it can be crafted anywhere.
+    pub building: Option<BuildingType>,
+}
+
+impl Recipe {
+    /// [`Recipe::is_available_at`] returns `true` if a [`Recipe`] can be crafted at
+    /// a `station`.
+    pub fn is_available_at(&self, station: Option<BuildingType>) -> bool {
+        self.building.is_none() || self.building == station
+    }
+
+    /// [`Recipe::get_missing_inputs`] returns every input of a [`Recipe`] that an
+    /// `inventory` doesn't have enough of, with the quantity still needed.
+    pub fn get_missing_inputs(&self, inventory: &Inventory) -> Vec<RecipeItem> {
+        self.inputs
+            .iter()
+            .filter_map(|input| {
+                let held = inventory.count(&input.item_id);
+                (held < input.quantity).then(|| RecipeItem {
+                    item_id: input.item_id.clone(),
+                    quantity: input.quantity - held,
+                })
+            })
+            .collect()
+    }
+
+    /// [`Recipe::can_craft`] returns `true` if a [`Recipe`] can be crafted at a
+    /// `station` with the items in an `inventory`.
+    pub fn can_craft(&self, inventory: &Inventory, station: Option<BuildingType>) -> bool {
+        self.is_available_at(station) && self.get_missing_inputs(inventory).is_empty()
+    }
+
+    /// [`Recipe::craft`] will attempt to consume the inputs of a [`Recipe`] from an
+    /// `inventory` and add its outputs, and return a [`CraftError`] explaining why
+    /// if it can't. The `inventory` is left untouched when crafting fails.
+    pub fn craft(
+        &self,
+        inventory: &mut Inventory,
+        items: &ItemCatalog,
+        station: Option<BuildingType>,
+    ) -> Result<(), CraftError> {
+        if let Some(building) = self.building
+            && !self.is_available_at(station)
+        {
+            return Err(CraftError::WrongBuilding(building));
+        }
+        let missing = self.get_missing_inputs(inventory);
+        if !missing.is_empty() {
+            let names = missing
+                .iter()
+                .map(|m| format!("{}x {}", m.quantity, get_item_name(items, &m.item_id)))
+                .collect();
+            return Err(CraftError::MissingIngredients(names));
+        }
+
+        let outputs: Vec<(&Item, u32)> = self
+            .outputs
+            .iter()
+            .filter_map(|o| items.get(&o.item_id).map(|item| (item, o.quantity)))
+            .collect();
+        // craft into a copy, so nothing changes unless every output fits
+        let mut crafted = inventory.clone();
+        for input in &self.inputs {
+            crafted.remove_item(&input.item_id, input.quantity);
+        }
+        if !outputs
+            .iter()
+            .all(|(item, quantity)| crafted.add_item(item, *quantity))
+        {
+            return Err(CraftError::InventoryFull);
+        }
+        *inventory = crafted;
+        Ok(())
+    }
+}
+
+fn get_item_name<'a>(items: &'a ItemCatalog, item_id: &'a str) -> &'a str {
+    items
+        .get(item_id)
+        .map_or(item_id, |item| item.name.as_str())
+}
+
+/// [`CraftError`] represents the reason a [`Recipe`] couldn't be crafted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CraftError {
+    /// [`CraftError::MissingIngredients`] lists the ingredients still needed.
+    MissingIngredients(Vec<String>),
+    /// [`CraftError::WrongBuilding`] is the [`BuildingType`] the [`Recipe`] needs.
+    WrongBuilding(BuildingType),
+    /// [`CraftError::InventoryFull`] means there is no room for the outputs.
+    InventoryFull,
+}
+
+impl fmt::Display for CraftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CraftError::MissingIngredients(names) => {
+                write!(f, "Missing ingredients: {}", names.join(", "))
+            }
+            CraftError::WrongBuilding(building) => write!(f, "Requires a {building}"),
+            CraftError::InventoryFull => write!(f, "Not enough room in your inventory"),
+        }
+    }
+}
+
+/// [`RecipeBook`] holds every [`Recipe`] in the game.
+#[derive(Clone, Debug, Default)]
+pub struct RecipeBook {
+    recipes: Vec<Recipe>,
+}
+
+impl RecipeBook {
+    /// [`RecipeBook::load`] reads and parses the recipe data file at `path`, checking
+    /// every [`RecipeItem`] against an [`ItemCatalog`].
+    pub fn load(path: &str, items: &ItemCatalog) -> Result<Self, RecipeError> {
+        let records = record::read_records(path)?;
+        Self::from_records(&records, items)
+    }
+
+    /// [`RecipeBook::parse`] parses the `source` of a recipe data file, where each
+    /// [`Recipe`] is a `[recipe id]` header followed by its fields.
+    pub fn parse(source: &str, items: &ItemCatalog) -> Result<Self, RecipeError> {
+        let records = record::parse_records(source)?;
+        Self::from_records(&records, items)
+    }
+
+    fn from_records(records: &[Record], items: &ItemCatalog) -> Result<Self, RecipeError> {
+        let mut book = RecipeBook::default();
+        for recipe_record in records.iter().filter(|r| r.kind == "recipe") {
+            if book.recipes.iter().any(|r| r.id == recipe_record.id) {
+                return Err(RecipeError::DuplicateId {
+                    id: recipe_record.id.clone(),
+                    line: recipe_record.line,
+                });
+            }
+            let inputs = parse_recipe_items(recipe_record, "input", items)?;
+            let outputs = parse_recipe_items(recipe_record, "output", items)?;
+            if outputs.is_empty() {
+                return Err(RecipeError::Data(DataError::MissingField {
+                    line: recipe_record.line,
+                    id: recipe_record.id.clone(),
+                    field: "output".to_owned(),
+                }));
+            }
+            book.recipes.push(Recipe {
+                id: recipe_record.id.clone(),
+                name: recipe_record.require("name")?.to_owned(),
+                inputs,
+                outputs,
+                building: recipe_record.parse_optional("building")?,
+            });
+        }
+        Ok(book)
+    }
+
+    /// [`RecipeBook::recipes`] returns every [`Recipe`] in the order they were defined.
+    pub fn recipes(&self) -> &[Recipe] {
+        &self.recipes
+    }
+}
+
+// parse every `key = item_id quantity` field of a recipe
+fn parse_recipe_items(
+    recipe_record: &Record,
+    key: &str,
+    items: &ItemCatalog,
+) -> Result<Vec<RecipeItem>, RecipeError> {
+    recipe_record
+        .get_all(key)
+        .into_iter()
+        .map(|(value, line)| {
+            let (item_id, quantity) = match value.split_once(' ') {
+                Some((item_id, quantity)) => {
+                    (item_id, record::parse_value(quantity.trim(), key, line)?)
+                }
+                None => (value, 1),
+            };
+            if items.get(item_id).is_none() {
+                return Err(RecipeError::UnknownItem {
+                    id: recipe_record.id.clone(),
+                    item_id: item_id.to_owned(),
+                    line,
+                });
+            }
+            Ok(RecipeItem {
+                item_id: item_id.to_owned(),
+                quantity,
+            })
+        })
+        .collect()
+}
+
+/// [`RecipeError`] represents a problem loading a [`RecipeBook`].
+#[derive(Debug)]
+pub enum RecipeError {
+    /// [`RecipeError::Data`] means the recipe data file couldn't be read or parsed.
+    Data(DataError),
+    /// [`RecipeError::DuplicateId`] means two [`Recipe`] were defined with the same `id`.
+    DuplicateId { id: String, line: usize },
+    /// [`RecipeError::UnknownItem`] means a [`Recipe`] uses an [`Item`] that isn't defined.
+    UnknownItem {
+        id: String,
+        item_id: String,
+        line: usize,
+    },
+}
+
+impl From<DataError> for RecipeError {
+    fn from(error: DataError) -> Self {
+        RecipeError::Data(error)
+    }
+}
+
+impl fmt::Display for RecipeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecipeError::Data(error) => write!(f, "recipe data error: {error}"),
+            RecipeError::DuplicateId { id, line } => {
+                write!(f, "line {line}: recipe `{id}` is defined more than once")
+            }
+            RecipeError::UnknownItem { id, item_id, line } => {
+                write!(
+                    f,
+                    "line {line}: recipe `{id}` uses unknown item `{item_id}`"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RecipeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: &str = "\
+[item iron_ore]
+name = Iron Ore
+sprite = icon_ore
+type = material
+max_stack = 10
+
+[item iron_ingot]
+name = Iron Ingot
+sprite = icon_ingot
+type = material
+max_stack = 10
+
+[item iron_helmet]
+name = Iron Helmet
+sprite = icon_helmet
+type = armor
+slot = head
+
+[item iron_boots]
+name = Iron Boots
+sprite = icon_boots
+type = armor
+slot = feet
+";
+
+    fn create_recipe(inputs: &[(&str, u32)], outputs: &[(&str, u32)]) -> Recipe {
+        let to_recipe_items = |pairs: &[(&str, u32)]| {
+            pairs
+                .iter()
+                .map(|(item_id, quantity)| RecipeItem {
+                    item_id: item_id.to_string(),
+                    quantity: *quantity,
+                })
+                .collect()
+        };
+        Recipe {
+            id: "test".to_owned(),
+            name: "Test".to_owned(),
+            inputs: to_recipe_items(inputs),
+            outputs: to_recipe_items(outputs),
+            building: None,
+        }
+    }
+
+    // an inventory with a free slot for each stack of ore, and `free_slots` more
+    fn create_inventory(items: &ItemCatalog, ore: u32, free_slots: usize) -> Inventory {
+        let mut inventory = Inventory::new(free_slots + 1);
+        inventory.add_item(items.get("iron_ore").unwrap(), ore);
+        inventory
+    }
+
+    #[test]
+    fn crafting_swaps_inputs_for_outputs() {
+        let items = ItemCatalog::parse(ITEMS).unwrap();
+        let mut inventory = create_inventory(&items, 4, 1);
+        let recipe = create_recipe(&[("iron_ore", 2)], &[("iron_ingot", 1)]);
+
+        assert_eq!(recipe.craft(&mut inventory, &items, None), Ok(()));
+        assert_eq!(inventory.count("iron_ore"), 2);
+        assert_eq!(inventory.count("iron_ingot"), 1);
+    }
+
+    #[test]
+    fn outputs_that_only_fit_alone_are_rejected_together() {
+        let items = ItemCatalog::parse(ITEMS).unwrap();
+        let mut inventory = create_inventory(&items, 4, 1);
+        let before = inventory.clone();
+
+        // either output fits in the last free slot, but not both of them
+        let recipe = create_recipe(&[("iron_ore", 2)], &[("iron_helmet", 1), ("iron_boots", 1)]);
+        assert_eq!(
+            recipe.craft(&mut inventory, &items, None),
+            Err(CraftError::InventoryFull)
+        );
+        assert_eq!(inventory, before);
+
+        // the same goes for an output that is listed twice
+        let recipe = create_recipe(
+            &[("iron_ore", 2)],
+            &[("iron_helmet", 1), ("iron_helmet", 1)],
+        );
+        assert_eq!(
+            recipe.craft(&mut inventory, &items, None),
+            Err(CraftError::InventoryFull)
+        );
+        assert_eq!(inventory, before);
+    }
+
+    #[test]
+    fn a_full_inventory_keeps_its_ingredients() {
+        let items = ItemCatalog::parse(ITEMS).unwrap();
+        let mut inventory = create_inventory(&items, 10, 0);
+        let before = inventory.clone();
+
+        let recipe = create_recipe(&[("iron_ore", 2)], &[("iron_helmet", 1)]);
+        assert_eq!(
+            recipe.craft(&mut inventory, &items, None),
+            Err(CraftError::InventoryFull)
+        );
+        assert_eq!(inventory, before);
+
+        // using up a whole stack frees the slot the output needs
+        let recipe = create_recipe(&[("iron_ore", 10)], &[("iron_helmet", 1)]);
+        assert_eq!(recipe.craft(&mut inventory, &items, None), Ok(()));
+        assert_eq!(inventory.count("iron_helmet"), 1);
+    }
+
+    #[test]
+    fn recipes_are_loaded_with_their_items() {
+        let items = ItemCatalog::parse(ITEMS).unwrap();
+        let source = "\
+[recipe smelt]
+name = Smelt
+input = iron_ore 2
+output = iron_ingot
+building = crafting_bench
+";
+        let book = RecipeBook::parse(source, &items).unwrap();
+        let recipe = &book.recipes()[0];
+        assert_eq!(
+            recipe.inputs,
+            vec![RecipeItem {
+                item_id: "iron_ore".to_owned(),
+                quantity: 2
+            }]
+        );
+        // a quantity can be left out when it is one
+        assert_eq!(recipe.outputs[0].quantity, 1);
+        assert_eq!(recipe.building, Some(BuildingType::CraftingBench));
+        assert!(!recipe.is_available_at(None));
+    }
+
+    #[test]
+    fn invalid_recipes_are_rejected() {
+        let items = ItemCatalog::parse(ITEMS).unwrap();
+
+        let source = "[recipe smelt]\nname = Smelt\ninput = gold_ore 2\noutput = iron_ingot\n";
+        assert!(matches!(
+            RecipeBook::parse(source, &items),
+            Err(RecipeError::UnknownItem { item_id, line: 3, .. }) if item_id == "gold_ore"
+        ));
+
+        let source = "[recipe smelt]\nname = Smelt\ninput = iron_ore 2\n";
+        assert!(matches!(
+            RecipeBook::parse(source, &items),
+            Err(RecipeError::Data(DataError::MissingField { field, .. })) if field == "output"
+        ));
+
+        let source = "[recipe a]\nname = A\noutput = iron_ingot\n[recipe a]\nname = A\noutput = iron_ingot\n";
+        assert!(matches!(
+            RecipeBook::parse(source, &items),
+            Err(RecipeError::DuplicateId { line: 4, .. })
+        ));
+
+        let source = "[recipe smelt]\nname = Smelt\noutput = iron_ingot many\n";
+        assert!(matches!(
+            RecipeBook::parse(source, &items),
+            Err(RecipeError::Data(DataError::InvalidField { line: 3, .. }))
+        ));
+    }
+}
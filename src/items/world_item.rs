@@ -0,0 +1,46 @@
+use super::item::Item;
+use crate::{maps::camera::Camera, renderer::render::Render};
+
+/// [`WORLD_ITEM_SIZE`] is the width and height in tiles of a [`WorldItem`].
+pub const WORLD_ITEM_SIZE: f32 = 1.;
+
+/// [`WorldItem`] is a `quantity` of an [`Item`] lying in the world, waiting to be
+/// picked up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorldItem {
+    /// [`WorldItem::item`] is the [`Item`] lying in the world.
+    pub item: Item,
+    /// [`WorldItem::quantity`] is how many of the [`Item`] there are.
+    pub quantity: u32,
+    /// [`WorldItem::x`] is the horizontal world location of a [`WorldItem`].
+    pub x: f32,
+    /// [`WorldItem::y`] is the vertical world location of a [`WorldItem`].
+    pub y: f32,
+}
+
+impl WorldItem {
+    /// [`WorldItem::new`] creates a new [`WorldItem`] at an (`x`, `y`) coordinate.
+    pub fn new(item: Item, quantity: u32, x: f32, y: f32) -> Self {
+        Self {
+            item,
+            quantity,
+            x,
+            y,
+        }
+    }
+
+    /// [`WorldItem::draw_world_item`] will draw a [`WorldItem`] where a [`Camera`]
+    /// sees it using a [`Render`] implementation.
+    pub fn draw_world_item<R: Render>(&self, camera: &Camera, renderer: &mut R) {
+        if camera.is_visible(self.x, self.y, WORLD_ITEM_SIZE, WORLD_ITEM_SIZE) {
+            let (x, y) = camera.world_to_screen(self.x, self.y);
+            renderer.draw_sprite(x, y, &self.item.sprite_id);
+        }
+    }
+
+    /// [`WorldItem::get_bounds`] returns the (`x`, `y`, `width`, `height`) area a
+    /// [`WorldItem`] takes up.
+    pub fn get_bounds(&self) -> (f32, f32, f32, f32) {
+        (self.x, self.y, WORLD_ITEM_SIZE, WORLD_ITEM_SIZE)
+    }
+}
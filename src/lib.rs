@@ -1,37 +1,71 @@
+pub mod data {
+    pub mod record;
+}
+
 pub mod events {
     pub mod crafting;
+    pub mod dialog;
     pub mod event;
     pub mod event_listener;
+    pub mod game_over;
+    pub mod gamepad;
     pub mod input;
+    pub mod input_map;
+    pub mod inventory;
     pub mod mouse;
+    pub mod paused;
     pub mod playing;
+    pub mod settings;
     pub mod title_screen;
 }
 
 pub mod game {
+    pub mod combat;
+    pub mod crafting;
+    pub mod dialog;
+    pub mod enemy;
+    pub mod game_over;
     pub mod mode;
+    pub mod movement;
+    pub mod npc;
+    pub mod pause;
     pub mod player;
+    pub mod random;
+    pub mod save;
     pub mod screen;
+    pub mod settings;
     pub mod state;
+    pub mod stats;
+    pub mod title_menu;
 }
 
 pub mod items {
+    pub mod catalog;
     pub mod currency;
+    pub mod equipment;
     pub mod inventory;
     pub mod item;
+    pub mod recipe;
+    pub mod world_item;
 }
 
 pub mod maps {
     pub mod building;
     pub mod building_type;
+    pub mod camera;
     pub mod map;
     pub mod tile;
 }
 
 pub mod renderer {
+    pub mod cell;
     pub mod color;
+    pub mod effect;
     pub mod graphics;
+    pub mod headless;
     pub mod render;
+    pub mod sprite;
+    pub mod terminal;
 }
 
 pub mod shaders {
@@ -41,10 +75,14 @@ pub mod shaders {
 pub mod ui {
     pub mod crafting;
     pub mod dialog;
+    pub mod game_over;
     pub mod hud;
     pub mod interface;
     pub mod inventory;
     pub mod main_menu;
+    pub mod panel;
+    pub mod pause;
     pub mod playing;
+    pub mod settings;
     pub mod status_bar;
 }
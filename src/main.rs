@@ -1,55 +1,123 @@
 use macroquad::time;
 use macroquad::window::Conf;
 use rust_and_ruin::game::screen::Screen;
+use rust_and_ruin::game::settings::{SETTINGS_PATH, Settings};
 use rust_and_ruin::renderer::graphics::GraphicsRenderer;
 use rust_and_ruin::renderer::render::TILE_SIZE;
+use rust_and_ruin::renderer::terminal::TerminalRenderer;
 use rust_and_ruin::{game::state::GameState, renderer::render::Render};
+use std::time::{Duration, Instant};
+
+const TERMINAL_FLAG: &str = "--terminal";
+const TERMINAL_FRAME_TIME: Duration = Duration::from_millis(33);
+// a terminal only reports key presses, so each one keeps the player walking this many
+// seconds, which is enough to get up to speed and to bridge the gap between key repeats
+const TERMINAL_KEY_HOLD_TIME: f32 = 0.25;
 
 // TODO: asset loading
 // main game loop function, generic over any Render implementation
-async fn run_game<R: Render>(mut renderer: R) -> Result<(), Box<dyn std::error::Error>> {
-    let mut game_state = GameState::new();
+async fn run_game<R: Render>(
+    mut renderer: R,
+    settings: Settings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut game_state = GameState::with_settings(settings);
+    game_state.load_game_assets()?;
     while !game_state.quit_game {
         // get the frame time to animate entities smoothly
         let delta_time = time::get_frame_time();
 
-        // handle any input from the player
-        renderer
-            .poll_input()
-            .into_iter()
-            .for_each(|i| game_state.handle_input(i, delta_time));
+        run_frame(&mut game_state, &mut renderer, delta_time);
 
-        // update the game state
-        game_state.update();
+        // wait until the next frame is drawn
+        macroquad::prelude::next_frame().await;
+    }
+    Ok(())
+}
+
+// terminal game loop function, which paces frames itself since there is no window
+fn run_terminal_game(
+    mut renderer: TerminalRenderer,
+    settings: Settings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut game_state = GameState::with_settings(settings);
+    game_state.movement.hold_time = TERMINAL_KEY_HOLD_TIME;
+    game_state.load_game_assets()?;
+    let mut last_frame = Instant::now();
+    while !game_state.quit_game {
+        let delta_time = last_frame.elapsed().as_secs_f32();
+        last_frame = Instant::now();
 
-        // draw the game screen
-        game_state.draw_screen(&mut renderer);
+        run_frame(&mut game_state, &mut renderer, delta_time);
+        renderer.present()?;
 
         // wait until the next frame is drawn
-        macroquad::prelude::next_frame().await;
+        std::thread::sleep(TERMINAL_FRAME_TIME.saturating_sub(last_frame.elapsed()));
     }
     Ok(())
 }
 
-fn window_config() -> Conf {
+// handle input, update and draw a single frame of the game
+fn run_frame<R: Render>(game_state: &mut GameState, renderer: &mut R, delta_time: f32) {
+    // handle any input from the player
+    renderer
+        .poll_input()
+        .into_iter()
+        .for_each(|i| game_state.handle_input(i, delta_time));
+
+    // keep the camera the same size as the screen
+    let (screen_width, screen_height) = renderer.get_screen_size();
+    let tile_size = renderer.get_tile_size();
+    game_state
+        .camera
+        .resize(screen_width / tile_size, screen_height / tile_size);
+
+    // update the game state
+    game_state.update(delta_time);
+
+    // draw the game screen
+    game_state.draw_screen(renderer);
+}
+
+fn window_config(settings: &Settings) -> Conf {
     Conf {
         window_title: "Rust & Ruin".to_owned(),
-        window_width: 1280,
-        window_height: 720,
-        high_dpi: true,
-        fullscreen: false,
+        window_width: settings.resolution.width as i32,
+        window_height: settings.resolution.height as i32,
+        high_dpi: settings.high_dpi,
+        fullscreen: settings.fullscreen,
         window_resizable: true,
         ..Default::default()
     }
 }
 
-#[macroquad::main(window_config)]
-async fn main() {
-    let mut renderer = GraphicsRenderer::new(TILE_SIZE);
+async fn run_graphics_game(settings: Settings) {
+    let mut renderer = GraphicsRenderer::new(settings.get_tile_size(), settings.input_map.clone());
     if let Err(e) = renderer.load_graphics_assets().await {
         panic!("Rust & Ruin encountered an issue loading art assets: {e}");
     }
-    if let Err(e) = run_game(renderer).await {
+    if let Err(e) = run_game(renderer, settings).await {
         panic!("Rust & Ruin encountered an error: {e}");
     }
 }
+
+fn main() {
+    // the settings are needed to open the window, so a broken settings file falls back
+    // to the defaults rather than stopping the game
+    let settings = Settings::load(SETTINGS_PATH).unwrap_or_else(|e| {
+        eprintln!("Rust & Ruin could not load the settings, using the defaults: {e}");
+        Settings::default()
+    });
+
+    // pick a backend by command-line flag, and default to a window
+    if std::env::args().any(|arg| arg == TERMINAL_FLAG) {
+        let renderer = match TerminalRenderer::new(TILE_SIZE, settings.input_map.clone()) {
+            Ok(renderer) => renderer,
+            Err(e) => panic!("Rust & Ruin encountered an issue opening the terminal: {e}"),
+        };
+        if let Err(e) = run_terminal_game(renderer, settings) {
+            panic!("Rust & Ruin encountered an error: {e}");
+        }
+    } else {
+        macroquad::Window::from_config(window_config(&settings), run_graphics_game(settings));
+    }
+}
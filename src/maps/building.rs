@@ -1,4 +1,5 @@
-use super::building_type::BuildingType;
+use super::{building_type::BuildingType, camera::Camera};
+use crate::renderer::render::Render;
 
 /// [`Building`] represents different buildings and their display properties in the game.
 #[derive(Clone, Copy, PartialEq)]
@@ -26,6 +27,13 @@ impl Building {
             height,
         }
     }
+
+    /// [`Building::draw_building`] will draw a [`Building`] where a [`Camera`] sees
+    /// it using a [`Render`] implementation.
+    pub fn draw_building<R: Render>(&self, camera: &Camera, renderer: &mut R) {
+        let (x, y) = camera.world_to_screen(self.x, self.y);
+        renderer.draw_sprite(x, y, self.building_type.get_sprite_id());
+    }
 }
 
 impl Default for Building {
@@ -1,5 +1,7 @@
+use crate::renderer::render::TILE_SIZE;
+
 /// [`BuildingType`] represents different buildings in the game.
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum BuildingType {
     /// [`BuildingType::CraftingBench`] represents a crafting bench [`BuildingType`].
     CraftingBench,
@@ -16,4 +18,49 @@ impl BuildingType {
             _ => "unknown",
         }
     }
+
+    /// [`BuildingType::get_size`] gets the width and height in tiles that a
+    /// [`BuildingType`] takes up on a [`Map`](super::map::Map).
+    pub fn get_size(&self) -> (f32, f32) {
+        match self {
+            BuildingType::CraftingBench => (121. / TILE_SIZE, 48. / TILE_SIZE),
+            BuildingType::Default => (0., 0.),
+        }
+    }
+
+    /// [`BuildingType::is_interactable`] returns `true` if a player can use a
+    /// [`BuildingType`] by walking up to it.
+    pub fn is_interactable(&self) -> bool {
+        matches!(self, BuildingType::CraftingBench)
+    }
+
+    /// [`BuildingType::get_id`] gets a [`&str`](str) that identifies a [`BuildingType`]
+    /// in data and save files.
+    pub fn get_id(&self) -> &str {
+        match self {
+            BuildingType::CraftingBench => "crafting_bench",
+            BuildingType::Default => "default",
+        }
+    }
+}
+
+impl std::str::FromStr for BuildingType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "crafting_bench" => Ok(BuildingType::CraftingBench),
+            "default" => Ok(BuildingType::Default),
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::fmt::Display for BuildingType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildingType::CraftingBench => write!(f, "Crafting Bench"),
+            BuildingType::Default => write!(f, "Building"),
+        }
+    }
 }
@@ -0,0 +1,123 @@
+use super::map::Map;
+
+const DEFAULT_VIEWPORT_WIDTH: f32 = 1280. / 24.;
+const DEFAULT_VIEWPORT_HEIGHT: f32 = 720. / 24.;
+
+/// [`Camera`] is the area of a [`Map`] that is visible on the screen, measured in
+/// tiles. It converts between screen and world coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    /// [`Camera::x`] is the horizontal world location of the left edge of the screen.
+    pub x: f32,
+    /// [`Camera::y`] is the vertical world location of the top edge of the screen.
+    pub y: f32,
+    /// [`Camera::width`] is how many tiles fit across the screen.
+    pub width: f32,
+    /// [`Camera::height`] is how many tiles fit down the screen.
+    pub height: f32,
+}
+
+impl Camera {
+    /// [`Camera::new`] creates a new [`Camera`] at the top left of the world that
+    /// shows `width` by `height` tiles.
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            x: 0.,
+            y: 0.,
+            width,
+            height,
+        }
+    }
+
+    /// [`Camera::resize`] changes how many tiles the [`Camera`] shows, i.e.: when the
+    /// screen changes size.
+    pub fn resize(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    /// [`Camera::follow`] centers the [`Camera`] on a (`x`, `y`) world coordinate,
+    /// without showing anything past the edges of a [`Map`]. A [`Map`] smaller than
+    /// the screen is centered instead.
+    pub fn follow(&mut self, x: f32, y: f32, map: &Map) {
+        self.x = clamp_axis(x - self.width / 2., self.width, map.width() as f32);
+        self.y = clamp_axis(y - self.height / 2., self.height, map.height() as f32);
+    }
+
+    /// [`Camera::world_to_screen`] converts a world (`x`, `y`) coordinate to a screen
+    /// tile coordinate.
+    pub fn world_to_screen(&self, x: f32, y: f32) -> (f32, f32) {
+        (x - self.x, y - self.y)
+    }
+
+    /// [`Camera::screen_to_world`] converts a screen tile (`x`, `y`) coordinate, like
+    /// a mouse click, to a world coordinate.
+    pub fn screen_to_world(&self, x: f32, y: f32) -> (f32, f32) {
+        (x + self.x, y + self.y)
+    }
+
+    /// [`Camera::is_visible`] returns `true` if any part of a `width` by `height`
+    /// area at a world (`x`, `y`) coordinate is on the screen.
+    pub fn is_visible(&self, x: f32, y: f32, width: f32, height: f32) -> bool {
+        x + width > self.x
+            && x < self.x + self.width
+            && y + height > self.y
+            && y < self.y + self.height
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera::new(DEFAULT_VIEWPORT_WIDTH, DEFAULT_VIEWPORT_HEIGHT)
+    }
+}
+
+// keep one axis of the camera within the map, or center the map if it is smaller
+fn clamp_axis(position: f32, view_size: f32, map_size: f32) -> f32 {
+    if map_size <= view_size {
+        (map_size - view_size) / 2.
+    } else {
+        position.clamp(0., map_size - view_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn following_centers_on_the_target() {
+        let mut camera = Camera::new(10., 6.);
+        camera.follow(20., 15., &Map::new(40, 30));
+        assert_eq!((camera.x, camera.y), (15., 12.));
+        assert_eq!(camera.world_to_screen(20., 15.), (5., 3.));
+        assert_eq!(camera.screen_to_world(5., 3.), (20., 15.));
+    }
+
+    #[test]
+    fn following_stops_at_the_edges_of_the_map() {
+        let map = Map::new(40, 30);
+        let mut camera = Camera::new(10., 6.);
+        camera.follow(1., 1., &map);
+        assert_eq!((camera.x, camera.y), (0., 0.));
+        camera.follow(39., 29., &map);
+        assert_eq!((camera.x, camera.y), (30., 24.));
+    }
+
+    #[test]
+    fn small_maps_are_centered() {
+        let mut camera = Camera::new(10., 6.);
+        camera.follow(1., 1., &Map::new(4, 30));
+        assert_eq!((camera.x, camera.y), (-3., 0.));
+        assert_eq!(clamp_axis(5., 6., 6.), 0.);
+    }
+
+    #[test]
+    fn only_areas_on_the_screen_are_visible() {
+        let camera = Camera::new(10., 6.);
+        assert!(camera.is_visible(9.5, 5.5, 1., 1.));
+        assert!(camera.is_visible(-0.5, 0., 1., 1.));
+        assert!(!camera.is_visible(10., 0., 1., 1.));
+        assert!(!camera.is_visible(-1., 0., 1., 1.));
+    }
+}
@@ -1,48 +1,178 @@
-use super::{building::Building, building_type::BuildingType, tile::Tile};
-use crate::renderer::{color::Color, render::Render};
+use super::{building::Building, building_type::BuildingType, camera::Camera, tile::Tile};
+use crate::{
+    data::record::{self, DataError, Record},
+    renderer::{color::Color, render::Render},
+};
+use std::fmt;
 
-const MAX_MAP_WIDTH: usize = 128;
-const MAX_MAP_HEIGHT: usize = 128;
-const MAX_MAP_BUILDINGS: usize = 10;
+/// [`STARTING_MAP_PATH`] is the map file a new game starts on.
+pub const STARTING_MAP_PATH: &str = "assets/maps/starting_area.txt";
+
+const DEFAULT_MAP_WIDTH: usize = 128;
+const DEFAULT_MAP_HEIGHT: usize = 128;
 
 /// [`Map`] represents an area in the game.
 pub struct Map {
-    /// [`Map::tiles`] is a collection of discrete areas of a [`Map`].
-    pub tiles: [[Tile; MAX_MAP_WIDTH]; MAX_MAP_HEIGHT],
+    /// [`Map::width`] is how many tiles across a [`Map`] is.
+    width: usize,
+    /// [`Map::height`] is how many tiles down a [`Map`] is.
+    height: usize,
+    /// [`Map::tiles`] is a collection of discrete areas of a [`Map`], row by row.
+    tiles: Vec<Tile>,
     /// [`Map::buildings`] is a collection of buildings in a [`Map`].
-    pub buildings: [Building; MAX_MAP_BUILDINGS],
+    pub buildings: Vec<Building>,
+    /// [`Map::spawn`] is the (`x`, `y`) coordinate the player starts at on a [`Map`].
+    pub spawn: (f32, f32),
 }
 
 impl Map {
-    /// [`Map::new`] creates a new [`Map`] with default [`Map::tiles`]
+    /// [`Map::new`] creates a new [`Map`] of `width` by `height` [`Tile::Floor`]
     /// and an empty collection of [`Map::buildings`].
-    pub fn new() -> Self {
+    pub fn new(width: usize, height: usize) -> Self {
         Self {
-            tiles: [[Tile::Floor; MAX_MAP_WIDTH]; MAX_MAP_HEIGHT],
-            buildings: [Building::default(); MAX_MAP_BUILDINGS],
+            width,
+            height,
+            tiles: vec![Tile::Floor; width * height],
+            buildings: Vec::new(),
+            spawn: (10., 10.),
         }
     }
 
-    /// [`Map::add_building`] will attempt to add a `building` to a [`Map`],
-    /// and return the result of the attempt.
-    pub fn add_building(&mut self, building: Building) -> bool {
-        if let Some(index) = self
-            .buildings
+    /// [`Map::width`] returns how many tiles across a [`Map`] is.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// [`Map::height`] returns how many tiles down a [`Map`] is.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// [`Map::load`] reads and validates the map file at `path`.
+    pub fn load(path: &str) -> Result<Self, MapError> {
+        let records = record::read_records(path)?;
+        let map_record = records
             .iter()
-            .position(|b| b.building_type == BuildingType::Default)
-        {
-            self.buildings[index] = building;
-            true
-        } else {
-            println!("Map: No space available for new building!");
-            false
+            .find(|r| r.kind == "map")
+            .ok_or(MapError::MissingMap)?;
+        Map::from_record(map_record)
+    }
+
+    /// [`Map::from_record`] builds a [`Map`] from a `[map id]` [`Record`], which has
+    /// a `row = ...` field for each row of tiles, a `spawn = x y` field and a
+    /// `building = type x y` field for each [`Building`]. The rows decide the
+    /// width and height of the [`Map`].
+    pub fn from_record(map_record: &Record) -> Result<Self, MapError> {
+        let rows = map_record.get_all("row");
+        if rows.is_empty() {
+            return Err(DataError::MissingField {
+                line: map_record.line,
+                id: map_record.id.clone(),
+                field: "row".to_owned(),
+            }
+            .into());
+        }
+        let width = rows[0].0.chars().count();
+        let mut map = Map::new(width, rows.len());
+        map.tiles.clear();
+        for (row, line) in rows {
+            if row.chars().count() != width {
+                return Err(MapError::Invalid {
+                    line,
+                    reason: format!("every row must be {width} tiles wide, like the first"),
+                });
+            }
+            for symbol in row.chars() {
+                let tile = Tile::from_symbol(symbol).ok_or_else(|| MapError::Invalid {
+                    line,
+                    reason: format!("`{symbol}` is not a tile"),
+                })?;
+                map.tiles.push(tile);
+            }
+        }
+
+        for (value, line) in map_record.get_all("building") {
+            let fields: Vec<&str> = value.split_whitespace().collect();
+            let [building_type, x, y] = fields[..] else {
+                return Err(MapError::Invalid {
+                    line,
+                    reason: "expected `building = type x y`".to_owned(),
+                });
+            };
+            let building_type: BuildingType = record::parse_value(building_type, "building", line)?;
+            let (x, y) = (
+                record::parse_value(x, "building", line)?,
+                record::parse_value(y, "building", line)?,
+            );
+            let (width, height) = building_type.get_size();
+            map.add_building(Building::new(building_type, x, y, width, height))
+                .map_err(|_| MapError::Invalid {
+                    line,
+                    reason: format!("the building at ({x}, {y}) is outside the map"),
+                })?;
+        }
+
+        let spawn = map_record.require("spawn")?;
+        let spawn_line = map_record
+            .get_all("spawn")
+            .first()
+            .map_or(map_record.line, |(_, line)| *line);
+        let Some((x, y)) = spawn.split_once(' ') else {
+            return Err(MapError::Invalid {
+                line: spawn_line,
+                reason: "expected `spawn = x y`".to_owned(),
+            });
+        };
+        map.spawn = (
+            record::parse_value(x.trim(), "spawn", spawn_line)?,
+            record::parse_value(y.trim(), "spawn", spawn_line)?,
+        );
+        if map.get_tile(map.spawn.0, map.spawn.1) != Some(Tile::Floor) {
+            return Err(MapError::Invalid {
+                line: spawn_line,
+                reason: "the spawn point must be on a floor tile".to_owned(),
+            });
+        }
+
+        Ok(map)
+    }
+
+    /// [`Map::to_record_string`] writes a [`Map`] as a `[map id]` record that
+    /// [`Map::from_record`] can read back.
+    pub fn to_record_string(&self, id: &str) -> String {
+        let mut map_record = format!("[map {id}]\n");
+        map_record += &format!("spawn = {} {}\n", self.spawn.0, self.spawn.1);
+        for b in &self.buildings {
+            map_record += &format!("building = {} {} {}\n", b.building_type.get_id(), b.x, b.y);
         }
+        for row in self.tiles.chunks(self.width.max(1)) {
+            let row: String = row.iter().map(|tile| tile.get_symbol()).collect();
+            map_record += &format!("row = {row}\n");
+        }
+        map_record
+    }
+
+    /// [`Map::add_building`] will add a `building` to a [`Map`] and mark every tile
+    /// under it as [`Tile::Building`], so it blocks movement. A
+    /// [`MapError::OutOfBounds`] is returned if any part of it is outside the [`Map`].
+    pub fn add_building(&mut self, building: Building) -> Result<(), MapError> {
+        let footprint = self
+            .get_area_indices(building.x, building.y, building.width, building.height)
+            .ok_or(MapError::OutOfBounds {
+                x: building.x,
+                y: building.y,
+            })?;
+        for index in footprint {
+            self.tiles[index] = Tile::Building;
+        }
+        self.buildings.push(building);
+        Ok(())
     }
 
     /// [`Map::get_building`] will query a set of (`x`, `y`) coordinates of a [`Map`]
     /// and return [`Some`] [`Building`] if one is found, and [`None`] otherwise.
     pub fn get_building(&self, x: f32, y: f32) -> Option<Building> {
-        self.buildings.into_iter().find(|b| {
+        self.buildings.iter().copied().find(|b| {
             let within_horizontal_bounds =
                 x.floor() >= b.x.floor() && x.floor() <= b.x.floor() + b.width.floor();
             let within_vertical_bounds =
@@ -51,59 +181,349 @@ impl Map {
         })
     }
 
-    /// [`Map::add_building`] will add a [`Tile`] to an (`x`, `y`) coordinate
-    /// of a [`Map`].
-    pub fn add_tile(&mut self, tile: Tile, x: f32, y: f32) {
-        self.tiles[x as usize][y as usize] = tile;
+    /// [`Map::add_tile`] will add a [`Tile`] to an (`x`, `y`) coordinate of a [`Map`],
+    /// and return a [`MapError::OutOfBounds`] if the coordinate is outside of it.
+    pub fn add_tile(&mut self, tile: Tile, x: f32, y: f32) -> Result<(), MapError> {
+        let index = self
+            .get_tile_index(x, y)
+            .ok_or(MapError::OutOfBounds { x, y })?;
+        self.tiles[index] = tile;
+        Ok(())
     }
 
     /// [`Map::get_tile`] will query an (`x`, `y`) coordinate of a [`Map`] for
     /// a tile and return [`Some`] [`Tile`] if one is found, and [`None`] otherwise.
     pub fn get_tile(&self, x: f32, y: f32) -> Option<Tile> {
-        let is_x_within_range = x > 0. && x < self.tiles[0].len() as f32;
-        let is_y_within_range = y > 0. && y < self.tiles.len() as f32;
+        self.get_tile_index(x, y).map(|index| self.tiles[index])
+    }
+
+    /// [`Map::is_area_passable`] returns `true` if every [`Tile`] touched by a
+    /// `width` by `height` rectangle at an (`x`, `y`) coordinate is a
+    /// [`Tile::Floor`]. Anything outside the [`Map`] is never passable.
+    pub fn is_area_passable(&self, x: f32, y: f32, width: f32, height: f32) -> bool {
+        self.get_area_indices(x, y, width, height)
+            .is_some_and(|indices| {
+                indices
+                    .iter()
+                    .all(|&index| self.tiles[index] == Tile::Floor)
+            })
+    }
+
+    /// [`Map::move_area`] moves a `size` rectangle at an (`x`, `y`) coordinate by a
+    /// `delta`, as long as every [`Tile`] under it stays passable, and returns where
+    /// it ends up. Each axis moves on its own, so a rectangle blocked on one axis
+    /// still slides along the other, and stops flush against whatever blocked it.
+    pub fn move_area(&self, x: f32, y: f32, size: (f32, f32), delta: (f32, f32)) -> (f32, f32) {
+        let (width, height) = size;
+        let x = get_next_position(x, delta.0, width, |x| {
+            self.is_area_passable(x, y, width, height)
+        });
+        let y = get_next_position(y, delta.1, height, |y| {
+            self.is_area_passable(x, y, width, height)
+        });
+        (x, y)
+    }
+
+    /// [`Map::find_passable_area`] returns [`Some`] (`x`, `y`) coordinate a whole
+    /// number of tiles from an (`x`, `y`) coordinate where a `size` rectangle is
+    /// passable and `is_free`, searching outward from the nearest, and [`None`] if
+    /// there is nowhere on the [`Map`] it fits.
+    pub fn find_passable_area<F: Fn(f32, f32) -> bool>(
+        &self,
+        x: f32,
+        y: f32,
+        size: (f32, f32),
+        is_free: F,
+    ) -> Option<(f32, f32)> {
+        let max_distance = self.width.max(self.height) as i32;
+        (0..=max_distance).find_map(|distance| {
+            // every offset on the square ring `distance` tiles out, nearest first
+            let mut ring: Vec<(i32, i32)> = (-distance..=distance)
+                .flat_map(|dy| (-distance..=distance).map(move |dx| (dx, dy)))
+                .filter(|(dx, dy)| dx.abs().max(dy.abs()) == distance)
+                .collect();
+            ring.sort_by_key(|(dx, dy)| dx * dx + dy * dy);
+            ring.into_iter()
+                .map(|(dx, dy)| (x + dx as f32, y + dy as f32))
+                .find(|&(x, y)| self.is_area_passable(x, y, size.0, size.1) && is_free(x, y))
+        })
+    }
+
+    // every tile index a rectangle overlaps, or none if it leaves the map
+    fn get_area_indices(&self, x: f32, y: f32, width: f32, height: f32) -> Option<Vec<usize>> {
+        let is_x_within_range = x >= 0. && x < self.width as f32 && x + width <= self.width as f32;
+        let is_y_within_range =
+            y >= 0. && y < self.height as f32 && y + height <= self.height as f32;
+        if !(is_x_within_range && is_y_within_range) {
+            return None;
+        }
+        // a rectangle ending exactly on a tile edge doesn't touch the next tile, but
+        // even an empty one touches the tile it is on
+        let (first_x, first_y) = (x as usize, y as usize);
+        let last_x = ((x + width).ceil() as usize).max(first_x + 1);
+        let last_y = ((y + height).ceil() as usize).max(first_y + 1);
+        Some(
+            (first_y..last_y)
+                .flat_map(|row| (first_x..last_x).map(move |column| row * self.width + column))
+                .collect(),
+        )
+    }
+
+    fn get_tile_index(&self, x: f32, y: f32) -> Option<usize> {
+        let is_x_within_range = x >= 0. && x < self.width as f32;
+        let is_y_within_range = y >= 0. && y < self.height as f32;
         if is_x_within_range && is_y_within_range {
-            Some(self.tiles[x as usize][y as usize])
+            Some(y as usize * self.width + x as usize)
         } else {
             None
         }
     }
 
-    /// [`Map::draw_map`] will render a map using a [`Render`] implementation.
-    pub fn draw_map<R: Render>(&self, renderer: &mut R) {
-        self.draw_tiles(renderer);
-        self.draw_buildings(renderer);
+    /// [`Map::draw_map`] will render the part of a map seen by a [`Camera`] using a
+    /// [`Render`] implementation.
+    pub fn draw_map<R: Render>(&self, camera: &Camera, renderer: &mut R) {
+        self.draw_tiles(camera, renderer);
+        self.draw_buildings(camera, renderer);
     }
 
-    /// [`Map::draw_tiles`] will render the tiles of a [`Map`] using a [`Render`] implementation.
-    pub fn draw_tiles<R: Render>(&self, renderer: &mut R) {
-        let horizontal_tiles_to_draw =
-            (renderer.get_screen_size().0 / renderer.get_tile_size()) as usize;
-        let vertical_tiles_to_draw =
-            (renderer.get_screen_size().1 / renderer.get_tile_size()) as usize;
-        for x in 0..horizontal_tiles_to_draw {
-            for y in 0..vertical_tiles_to_draw {
-                let (char_to_draw, fg_color, bg_color) = match self.tiles[x][y] {
+    /// [`Map::draw_tiles`] will render the tiles of a [`Map`] seen by a [`Camera`]
+    /// using a [`Render`] implementation.
+    pub fn draw_tiles<R: Render>(&self, camera: &Camera, renderer: &mut R) {
+        // only draw tiles on the screen, including partly visible ones on the edges
+        let first_x = camera.x.floor().max(0.) as usize;
+        let first_y = camera.y.floor().max(0.) as usize;
+        let last_x = ((camera.x + camera.width).ceil().max(0.) as usize).min(self.width);
+        let last_y = ((camera.y + camera.height).ceil().max(0.) as usize).min(self.height);
+        for x in first_x..last_x {
+            for y in first_y..last_y {
+                let (char_to_draw, fg_color, bg_color) = match self.tiles[y * self.width + x] {
                     Tile::Floor => (' ', Color::Transparent, Color::DarkGrey),
                     Tile::Wall => (' ', Color::Transparent, Color::Black),
                     Tile::Building => (' ', Color::Transparent, Color::Transparent),
                 };
-                renderer.draw_char(x as f32, y as f32, char_to_draw, fg_color, bg_color);
+                let (screen_x, screen_y) = camera.world_to_screen(x as f32, y as f32);
+                renderer.draw_char(screen_x, screen_y, char_to_draw, fg_color, bg_color);
             }
         }
     }
 
-    /// [`Map::draw_buildings`] will render the buildings of a [`Map`] using a [`Render`] implementation.
-    pub fn draw_buildings<R: Render>(&self, renderer: &mut R) {
+    /// [`Map::draw_buildings`] will render the buildings of a [`Map`] seen by a
+    /// [`Camera`] using a [`Render`] implementation.
+    pub fn draw_buildings<R: Render>(&self, camera: &Camera, renderer: &mut R) {
         self.buildings
-            .into_iter()
-            .filter(|b| b.building_type != BuildingType::Default)
-            .for_each(|b| renderer.draw_sprite(b.x, b.y, b.building_type.get_sprite_id()));
+            .iter()
+            .filter(|b| camera.is_visible(b.x, b.y, b.width, b.height))
+            .for_each(|b| b.draw_building(camera, renderer));
+    }
+}
+
+// move one axis of a box of `size` by `delta`, or as far as `is_passable` allows
+fn get_next_position(
+    position: f32,
+    delta: f32,
+    size: f32,
+    is_passable: impl Fn(f32) -> bool,
+) -> f32 {
+    if delta == 0. {
+        return position;
+    }
+    let target = position + delta;
+    if is_passable(target) {
+        return target;
+    }
+    // line up with the edge of the tile that is in the way
+    let flush = if delta > 0. {
+        (target + size).floor() - size
+    } else {
+        target.ceil()
+    };
+    if (flush - position) * delta > 0. && is_passable(flush) {
+        flush
+    } else {
+        position
     }
 }
 
 impl Default for Map {
     fn default() -> Self {
-        Map::new()
+        Map::new(DEFAULT_MAP_WIDTH, DEFAULT_MAP_HEIGHT)
+    }
+}
+
+/// [`MapError`] represents a problem loading a [`Map`].
+#[derive(Debug)]
+pub enum MapError {
+    /// [`MapError::Data`] means the map file couldn't be read or parsed.
+    Data(DataError),
+    /// [`MapError::MissingMap`] means the map file has no `[map id]` section.
+    MissingMap,
+    /// [`MapError::Invalid`] means the map on `line` breaks a rule, for a `reason`.
+    Invalid { line: usize, reason: String },
+    /// [`MapError::OutOfBounds`] means an (`x`, `y`) coordinate is outside of the map.
+    OutOfBounds { x: f32, y: f32 },
+}
+
+impl From<DataError> for MapError {
+    fn from(error: DataError) -> Self {
+        MapError::Data(error)
+    }
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapError::Data(error) => write!(f, "map data error: {error}"),
+            MapError::MissingMap => write!(f, "the map file has no `[map id]` section"),
+            MapError::Invalid { line, reason } => write!(f, "line {line}: {reason}"),
+            MapError::OutOfBounds { x, y } => write!(f, "({x}, {y}) is outside of the map"),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 4 by 3 map with a wall in the first corner and a building in the last, so a
+    // wrong index shows up as the wrong tile
+    fn create_test_map() -> Map {
+        let mut map = Map::new(4, 3);
+        map.add_tile(Tile::Wall, 0., 0.).unwrap();
+        map.add_tile(Tile::Building, 3., 2.).unwrap();
+        map
+    }
+
+    #[test]
+    fn get_tile_includes_first_column() {
+        let map = create_test_map();
+        assert_eq!(map.get_tile(0., 0.), Some(Tile::Wall));
+        assert_eq!(map.get_tile(0.5, 1.), Some(Tile::Floor));
+    }
+
+    #[test]
+    fn get_tile_includes_last_column_and_row() {
+        let map = create_test_map();
+        assert_eq!(map.get_tile(3., 2.), Some(Tile::Building));
+        assert_eq!(map.get_tile(3.99, 2.99), Some(Tile::Building));
+        assert_eq!(map.get_tile(3.5, 0.), Some(Tile::Floor));
+        assert_eq!(map.get_tile(1., 2.5), Some(Tile::Floor));
+    }
+
+    #[test]
+    fn get_tile_rejects_negative_coordinates() {
+        let map = create_test_map();
+        // these would truncate to the first column or row if they weren't rejected
+        assert_eq!(map.get_tile(-0.5, 1.), None);
+        assert_eq!(map.get_tile(1., -0.1), None);
+        assert_eq!(map.get_tile(-1., -1.), None);
+    }
+
+    #[test]
+    fn get_tile_rejects_coordinates_past_the_edge() {
+        let map = create_test_map();
+        assert_eq!(map.get_tile(4., 0.), None);
+        assert_eq!(map.get_tile(0., 3.), None);
+        assert_eq!(map.get_tile(100., 100.), None);
+    }
+
+    #[test]
+    fn find_passable_area_skips_walls_and_taken_spots() {
+        let mut map = create_test_map();
+        map.add_tile(Tile::Wall, 1., 0.).unwrap();
+        assert_eq!(
+            map.find_passable_area(2., 1., (1., 1.), |_, _| true),
+            Some((2., 1.))
+        );
+        // the wall to the top left is passed over for the nearest floor
+        assert_eq!(
+            map.find_passable_area(1., 0., (1., 1.), |_, _| true),
+            Some((2., 0.))
+        );
+        assert_eq!(
+            map.find_passable_area(2., 1., (1., 1.), |x, y| (x, y) != (2., 1.)),
+            Some((2., 0.))
+        );
+        assert_eq!(map.find_passable_area(0., 0., (1., 1.), |_, _| false), None);
+    }
+
+    #[test]
+    fn add_tile_rejects_coordinates_outside_the_map() {
+        let mut map = create_test_map();
+        assert!(matches!(
+            map.add_tile(Tile::Wall, 4., 0.),
+            Err(MapError::OutOfBounds { .. })
+        ));
+        assert!(matches!(
+            map.add_tile(Tile::Wall, -1., 0.),
+            Err(MapError::OutOfBounds { .. })
+        ));
+    }
+
+    const TEST_MAP: &str = "\
+[map test]
+spawn = 1 1
+building = crafting_bench 1 2
+row = ########
+row = #......#
+row = #......#
+row = #......#
+row = #......#
+row = ########
+";
+
+    // parse a map from `source`, which holds a single `[map id]` record
+    fn parse_map(source: &str) -> Result<Map, MapError> {
+        Map::from_record(&record::parse_records(source).unwrap()[0])
+    }
+
+    #[test]
+    fn maps_are_loaded_with_their_buildings() {
+        let map = parse_map(TEST_MAP).unwrap();
+        assert_eq!((map.width(), map.height()), (8, 6));
+        assert_eq!(map.spawn, (1., 1.));
+        assert_eq!(map.get_tile(0., 5.), Some(Tile::Wall));
+        assert_eq!(map.get_tile(2., 3.), Some(Tile::Building));
+        assert!(map.get_building(2., 3.).is_some());
+
+        let saved = map.to_record_string("test");
+        assert_eq!(parse_map(&saved).unwrap().to_record_string("test"), saved);
+    }
+
+    #[test]
+    fn invalid_maps_are_rejected_with_their_line() {
+        let uneven = TEST_MAP.replace(
+            "row = #......#\nrow = ########",
+            "row = #.....#\nrow = ########",
+        );
+        assert!(matches!(
+            parse_map(&uneven),
+            Err(MapError::Invalid { line: 8, .. })
+        ));
+
+        let unknown_tile = TEST_MAP.replacen("#......#", "#..~...#", 1);
+        assert!(matches!(
+            parse_map(&unknown_tile),
+            Err(MapError::Invalid { line: 5, .. })
+        ));
+
+        let outside = TEST_MAP.replace("crafting_bench 1 2", "crafting_bench 3 2");
+        assert!(matches!(
+            parse_map(&outside),
+            Err(MapError::Invalid { line: 3, .. })
+        ));
+
+        let on_a_wall = TEST_MAP.replace("spawn = 1 1", "spawn = 0 0");
+        assert!(matches!(
+            parse_map(&on_a_wall),
+            Err(MapError::Invalid { line: 2, .. })
+        ));
+
+        let no_rows = "[map test]\nspawn = 1 1\n";
+        assert!(matches!(
+            parse_map(no_rows),
+            Err(MapError::Data(DataError::MissingField { field, .. })) if field == "row"
+        ));
     }
 }
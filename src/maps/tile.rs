@@ -8,3 +8,26 @@ pub enum Tile {
     /// [`Tile::Building`] represents an impassable area of a [`Building`](super::building::Building).
     Building,
 }
+
+impl Tile {
+    /// [`Tile::get_symbol`] gets the character a [`Tile`] is written as in map and
+    /// save files.
+    pub fn get_symbol(&self) -> char {
+        match self {
+            Tile::Floor => '.',
+            Tile::Wall => '#',
+            Tile::Building => 'B',
+        }
+    }
+
+    /// [`Tile::from_symbol`] returns [`Some`] [`Tile`] written as `symbol` in map and
+    /// save files, and [`None`] otherwise.
+    pub fn from_symbol(symbol: char) -> Option<Tile> {
+        match symbol {
+            '.' => Some(Tile::Floor),
+            '#' => Some(Tile::Wall),
+            'B' => Some(Tile::Building),
+            _ => None,
+        }
+    }
+}
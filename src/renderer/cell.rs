@@ -0,0 +1,100 @@
+use super::color::Color;
+
+/// [`Cell`] is a single character position of a [`CellGrid`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cell {
+    /// [`Cell::character`] is the character drawn in a [`Cell`].
+    pub character: char,
+    /// [`Cell::fg_color`] is the [`Color`] of the [`Cell::character`].
+    pub fg_color: Color,
+    /// [`Cell::bg_color`] is the [`Color`] behind the [`Cell::character`].
+    pub bg_color: Color,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            character: ' ',
+            fg_color: Color::White,
+            bg_color: Color::Black,
+        }
+    }
+}
+
+/// [`CellGrid`] is a screen of [`Cell`] where every [`Cell`] represents one tile,
+/// used by renderers that draw characters instead of pixels.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CellGrid {
+    columns: usize,
+    rows: usize,
+    cells: Vec<Cell>,
+}
+
+impl CellGrid {
+    /// [`CellGrid::new`] creates a new [`CellGrid`] filled with default [`Cell`].
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            cells: vec![Cell::default(); columns * rows],
+        }
+    }
+
+    /// [`CellGrid::columns`] returns the width of a [`CellGrid`].
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// [`CellGrid::rows`] returns the height of a [`CellGrid`].
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// [`CellGrid::cells`] returns every [`Cell`] of a [`CellGrid`], row by row.
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// [`CellGrid::get`] returns [`Some`] [`Cell`] at a `column` and `row` if it
+    /// is within the [`CellGrid`], and [`None`] otherwise.
+    pub fn get(&self, column: usize, row: usize) -> Option<Cell> {
+        if column < self.columns && row < self.rows {
+            Some(self.cells[row * self.columns + column])
+        } else {
+            None
+        }
+    }
+
+    /// [`CellGrid::set`] draws a `character` at a `column` and `row`. Any
+    /// [`Color::Transparent`] color keeps whatever was drawn underneath, and
+    /// positions outside of the [`CellGrid`] are ignored.
+    pub fn set(&mut self, column: f32, row: f32, character: char, fg: Color, bg: Color) {
+        if column < 0. || row < 0. || column >= self.columns as f32 || row >= self.rows as f32 {
+            return;
+        }
+        let index = row as usize * self.columns + column as usize;
+        let cell = &mut self.cells[index];
+        if fg != Color::Transparent {
+            cell.fg_color = fg;
+            cell.character = character;
+        } else if bg != Color::Transparent {
+            cell.character = character;
+        }
+        if bg != Color::Transparent {
+            cell.bg_color = bg;
+        }
+    }
+
+    /// [`CellGrid::row_text`] returns the characters of a `row` as a [`String`].
+    pub fn row_text(&self, row: usize) -> String {
+        (0..self.columns)
+            .filter_map(|column| self.get(column, row))
+            .map(|cell| cell.character)
+            .collect()
+    }
+
+    /// [`CellGrid::clear`] resets every [`Cell`] of a [`CellGrid`].
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::default());
+    }
+}
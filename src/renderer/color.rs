@@ -1,5 +1,5 @@
 /// [`Color`] contains common colors used throughout the game.
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Color {
     Black,
     White,
@@ -0,0 +1,17 @@
+/// [`EffectId`] represents the different effects that can be drawn behind a screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EffectId {
+    /// [`EffectId::Starfield`] is a field of stars flying towards the screen.
+    Starfield,
+    /// [`EffectId::Dim`] darkens everything drawn so far, to set a menu apart from
+    /// what is behind it.
+    Dim,
+}
+
+/// [`EffectParams`] are the settings an [`EffectId`] is drawn with. Each
+/// [`Render`](super::render::Render) implementation decides how to use them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EffectParams {
+    /// [`EffectParams::direction_modifier`] changes the direction an effect moves in.
+    pub direction_modifier: f32,
+}
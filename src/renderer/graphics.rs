@@ -1,5 +1,16 @@
-use super::{color, render::Render};
-use crate::events::{input::InputEvent, mouse};
+use super::{
+    color,
+    effect::{EffectId, EffectParams},
+    render::{Render, TILE_SIZE},
+    sprite,
+};
+#[cfg(feature = "gamepad")]
+use crate::events::gamepad::GilrsGamepad;
+use crate::events::{
+    input::InputEvent,
+    input_map::{Binding, InputMap, Key, KeyboardState, Modifiers},
+    mouse,
+};
 use crate::shaders::starfield;
 use macroquad::color::Color;
 use macroquad::prelude::animation::{AnimatedSprite, Animation};
@@ -7,6 +18,9 @@ use macroquad::prelude::*;
 use macroquad::ui::{hash, root_ui, widgets};
 use std::collections::HashMap;
 
+// how dark the screen gets when it is dimmed, from `0.` to `1.`
+const DIM_ALPHA: f32 = 0.6;
+
 /// [`GraphicsRenderer`] is an implementation of [`Render`] that draws basic 2d graphics.
 pub struct GraphicsRenderer {
     textures: HashMap<String, Texture2D>,
@@ -14,16 +28,24 @@ pub struct GraphicsRenderer {
     animations: HashMap<String, AnimatedSprite>,
     tile_size: f32,
     shaders: [Material; 1],
+    last_mouse_position: (f32, f32),
+    input_map: InputMap,
+    #[cfg(feature = "gamepad")]
+    gamepad: Option<GilrsGamepad>,
 }
 
 impl GraphicsRenderer {
-    pub fn new(tile_size: f32) -> Self {
+    pub fn new(tile_size: f32, input_map: InputMap) -> Self {
         Self {
             textures: HashMap::new(),
             sprite_rects: HashMap::new(),
             animations: HashMap::new(),
             tile_size,
             shaders: [starfield::create_starfield_shader()],
+            last_mouse_position: (0., 0.),
+            input_map,
+            #[cfg(feature = "gamepad")]
+            gamepad: GilrsGamepad::new(),
         }
     }
 
@@ -45,25 +67,26 @@ impl GraphicsRenderer {
 
         self.sprite_rects
             .insert("crafting_bench".to_string(), Rect::new(0., 0., 121., 48.));
+        let (width, height) = sprite::CHARACTER_SPRITE_SIZE;
         self.sprite_rects.insert(
             "player_base".to_string(),
-            self.map_sprite_coords_to_rect(0., 49., 26., 62., 3, 2),
+            self.map_sprite_coords_to_rect(0., 49., width, height, 3, 2),
         );
         self.sprite_rects.insert(
             "player_back".to_string(),
-            self.map_sprite_coords_to_rect(0., 49., 26., 62., 1, 2),
+            self.map_sprite_coords_to_rect(0., 49., width, height, 1, 2),
         );
         self.sprite_rects.insert(
             "player_left".to_string(),
-            self.map_sprite_coords_to_rect(0., 49., 26., 62., 4, 2),
+            self.map_sprite_coords_to_rect(0., 49., width, height, 4, 2),
         );
         self.sprite_rects.insert(
             "player_right".to_string(),
-            self.map_sprite_coords_to_rect(0., 49., 26., 62., 2, 2),
+            self.map_sprite_coords_to_rect(0., 49., width, height, 2, 2),
         );
         self.sprite_rects.insert(
             "player_interact".to_string(),
-            self.map_sprite_coords_to_rect(0., 49., 26., 62., 3, 2),
+            self.map_sprite_coords_to_rect(0., 49., width, height, 3, 2),
         );
 
         self.animations.insert(
@@ -131,6 +154,18 @@ impl GraphicsRenderer {
         }
     }
 
+    fn map_effect_to_shader(&self, effect: EffectId) -> Option<&Material> {
+        match effect {
+            EffectId::Starfield => self.shaders.first(),
+            EffectId::Dim => None,
+        }
+    }
+
+    // how much bigger sprites are drawn than their art, to follow the ui scale
+    fn get_scale(&self) -> f32 {
+        self.tile_size / TILE_SIZE
+    }
+
     fn map_color_to_macroquad(&self, color: color::Color) -> macroquad::prelude::Color {
         match color {
             color::Color::Black => BLACK,
@@ -190,12 +225,14 @@ impl Render for GraphicsRenderer {
                     WHITE,
                     DrawTextureParams {
                         source: Some(*rect),
+                        dest_size: Some(rect.size() * self.get_scale()),
                         ..Default::default()
                     },
                 );
             } else {
-                // sprite not found
-                self.draw_char(x, y, '?', color::Color::Red, color::Color::Black);
+                // no art for the sprite, so draw its glyph instead
+                let (glyph, glyph_color) = sprite::get_sprite_glyph(sprite_id);
+                self.draw_char(x, y, glyph, glyph_color, color::Color::Black);
             }
         } else {
             // sheet not loaded
@@ -221,6 +258,7 @@ impl Render for GraphicsRenderer {
                 WHITE,
                 DrawTextureParams {
                     source: Some(animation.frame().source_rect),
+                    dest_size: Some(animation.frame().dest_size * self.get_scale()),
                     ..Default::default()
                 },
             );
@@ -313,25 +351,11 @@ impl Render for GraphicsRenderer {
     fn poll_input(&mut self) -> Vec<InputEvent> {
         let mut events: Vec<InputEvent> = Vec::new();
 
-        // known keyboard character input
-        if is_key_down(KeyCode::Up) || is_key_down(KeyCode::W) {
-            events.push(InputEvent::Up);
-        }
-        if is_key_down(KeyCode::Down) || is_key_down(KeyCode::S) {
-            events.push(InputEvent::Down);
-        }
-        if is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) {
-            events.push(InputEvent::Left);
-        }
-        if is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) {
-            events.push(InputEvent::Right);
-        }
-        if is_key_pressed(KeyCode::E) || is_key_pressed(KeyCode::Enter) {
-            events.push(InputEvent::Interact);
-        }
-        if is_key_pressed(KeyCode::Escape) {
-            events.push(InputEvent::Quit);
-        }
+        // any key pressed, ahead of what it is bound to, so it can be rebound
+        events.extend(Binding::get_pressed(&MacroquadKeyboard).map(InputEvent::KeyPressed));
+
+        // bound keyboard input
+        events.extend(self.input_map.map_input(&MacroquadKeyboard));
 
         // general keyboard character input
         if let Some(c) = get_char_pressed() {
@@ -346,6 +370,13 @@ impl Render for GraphicsRenderer {
         let (screen_width_chars, screen_height_chars) = self.get_screen_size();
         let tile_size = self.get_tile_size();
         if mouse_x_pixels < screen_width_chars && mouse_y_pixels < screen_height_chars {
+            if (mouse_x_pixels, mouse_y_pixels) != self.last_mouse_position {
+                self.last_mouse_position = (mouse_x_pixels, mouse_y_pixels);
+                events.push(InputEvent::MouseMove {
+                    x: mouse_x_pixels / tile_size,
+                    y: mouse_y_pixels / tile_size,
+                });
+            }
             if is_mouse_button_pressed(MouseButton::Left) {
                 events.push(InputEvent::MouseClick {
                     x: mouse_x_pixels / tile_size,
@@ -369,14 +400,120 @@ impl Render for GraphicsRenderer {
             }
         }
 
+        // gamepad input
+        #[cfg(feature = "gamepad")]
+        if let Some(gamepad) = &mut self.gamepad {
+            events.extend(self.input_map.map_gamepad(&gamepad.poll()));
+        }
+
         events
     }
 
-    fn get_shader_material(&self, shader_index: usize) -> Option<Material> {
-        if self.shaders.len() >= shader_index {
-            Some(self.shaders[shader_index])
-        } else {
-            None
+    // draw effects with their shader onto a low resolution texture stretched over the screen
+    fn draw_background_effect(&mut self, effect: EffectId, params: EffectParams) {
+        let (screen_w, screen_h) = self.get_screen_size();
+        if effect == EffectId::Dim {
+            draw_rectangle(
+                0.,
+                0.,
+                screen_w,
+                screen_h,
+                Color::new(0., 0., 0., DIM_ALPHA),
+            );
+            return;
+        }
+        if let Some(material) = self.map_effect_to_shader(effect) {
+            let render_target = render_target(320, 150);
+            render_target.texture.set_filter(FilterMode::Nearest);
+            material.set_uniform("iResolution", (screen_w, screen_h));
+            material.set_uniform("direction_modifier", params.direction_modifier);
+            gl_use_material(*material);
+            draw_texture_ex(
+                render_target.texture,
+                0.,
+                0.,
+                WHITE,
+                DrawTextureParams {
+                    dest_size: Some(vec2(screen_w, screen_h)),
+                    ..Default::default()
+                },
+            );
+            gl_use_default_material();
         }
     }
 }
+
+// the keyboard as macroquad sees it this frame
+struct MacroquadKeyboard;
+
+impl KeyboardState for MacroquadKeyboard {
+    fn is_key_down(&self, key: Key) -> bool {
+        map_key_to_macroquad(key).is_some_and(is_key_down)
+    }
+
+    fn is_key_pressed(&self, key: Key) -> bool {
+        map_key_to_macroquad(key).is_some_and(is_key_pressed)
+    }
+
+    fn get_modifiers(&self) -> Modifiers {
+        Modifiers {
+            shift: is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift),
+            ctrl: is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl),
+            alt: is_key_down(KeyCode::LeftAlt) || is_key_down(KeyCode::RightAlt),
+        }
+    }
+}
+
+fn map_key_to_macroquad(key: Key) -> Option<KeyCode> {
+    let key_code = match key {
+        Key::Up => KeyCode::Up,
+        Key::Down => KeyCode::Down,
+        Key::Left => KeyCode::Left,
+        Key::Right => KeyCode::Right,
+        Key::Enter => KeyCode::Enter,
+        Key::Escape => KeyCode::Escape,
+        Key::Space => KeyCode::Space,
+        Key::Tab => KeyCode::Tab,
+        Key::Backspace => KeyCode::Backspace,
+        Key::Char(c) => match c {
+            'A' => KeyCode::A,
+            'B' => KeyCode::B,
+            'C' => KeyCode::C,
+            'D' => KeyCode::D,
+            'E' => KeyCode::E,
+            'F' => KeyCode::F,
+            'G' => KeyCode::G,
+            'H' => KeyCode::H,
+            'I' => KeyCode::I,
+            'J' => KeyCode::J,
+            'K' => KeyCode::K,
+            'L' => KeyCode::L,
+            'M' => KeyCode::M,
+            'N' => KeyCode::N,
+            'O' => KeyCode::O,
+            'P' => KeyCode::P,
+            'Q' => KeyCode::Q,
+            'R' => KeyCode::R,
+            'S' => KeyCode::S,
+            'T' => KeyCode::T,
+            'U' => KeyCode::U,
+            'V' => KeyCode::V,
+            'W' => KeyCode::W,
+            'X' => KeyCode::X,
+            'Y' => KeyCode::Y,
+            'Z' => KeyCode::Z,
+            '0' => KeyCode::Key0,
+            '1' => KeyCode::Key1,
+            '2' => KeyCode::Key2,
+            '3' => KeyCode::Key3,
+            '4' => KeyCode::Key4,
+            '5' => KeyCode::Key5,
+            '6' => KeyCode::Key6,
+            '7' => KeyCode::Key7,
+            '8' => KeyCode::Key8,
+            '9' => KeyCode::Key9,
+            _ => return None,
+        },
+    };
+    Some(key_code)
+}
@@ -0,0 +1,305 @@
+use super::{
+    cell::CellGrid,
+    color::Color,
+    effect::{EffectId, EffectParams},
+    render::Render,
+};
+use crate::events::input::InputEvent;
+use std::collections::VecDeque;
+
+/// [`SpritePlacement`] records a sprite or animation drawn by a [`HeadlessRenderer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpritePlacement {
+    /// [`SpritePlacement::x`] is the horizontal tile location of the sprite.
+    pub x: f32,
+    /// [`SpritePlacement::y`] is the vertical tile location of the sprite.
+    pub y: f32,
+    /// [`SpritePlacement::sprite_id`] is the sprite id, or the sheet id of an animation.
+    pub sprite_id: String,
+    /// [`SpritePlacement::animation`] is [`Some`] animation name if the sprite was
+    /// drawn with [`Render::draw_animation`], and [`None`] otherwise.
+    pub animation: Option<String>,
+}
+
+/// [`TextPlacement`] records some text drawn by a [`HeadlessRenderer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextPlacement {
+    /// [`TextPlacement::x`] is the horizontal pixel location of the text.
+    pub x: f32,
+    /// [`TextPlacement::y`] is the vertical pixel location of the text.
+    pub y: f32,
+    /// [`TextPlacement::text`] is the text that was drawn.
+    pub text: String,
+    /// [`TextPlacement::fg_color`] is the [`Color`] of the text.
+    pub fg_color: Color,
+    /// [`TextPlacement::bg_color`] is the [`Color`] behind the text.
+    pub bg_color: Color,
+}
+
+/// [`Frame`] is everything a [`HeadlessRenderer`] has drawn since the screen was
+/// last cleared.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Frame {
+    /// [`Frame::cells`] is every character drawn, where each cell is one tile.
+    pub cells: CellGrid,
+    /// [`Frame::sprites`] is every sprite and animation drawn, in draw order.
+    pub sprites: Vec<SpritePlacement>,
+    /// [`Frame::texts`] is every piece of text drawn, in draw order.
+    pub texts: Vec<TextPlacement>,
+    /// [`Frame::effects`] is every background effect drawn, in draw order.
+    pub effects: Vec<(EffectId, EffectParams)>,
+}
+
+impl Frame {
+    /// [`Frame::contains_text`] returns `true` if any drawn text contains `text`.
+    pub fn contains_text(&self, text: &str) -> bool {
+        self.texts.iter().any(|t| t.text.contains(text))
+    }
+
+    /// [`Frame::find_sprite`] returns [`Some`] [`SpritePlacement`] for the first
+    /// sprite drawn with `sprite_id`, and [`None`] otherwise.
+    pub fn find_sprite(&self, sprite_id: &str) -> Option<&SpritePlacement> {
+        self.sprites.iter().find(|s| s.sprite_id == sprite_id)
+    }
+}
+
+/// [`HeadlessRenderer`] is an implementation of [`Render`] that draws nothing, and
+/// instead records every draw call into a [`Frame`] that can be inspected. Input is
+/// read from a scripted queue of [`InputEvent`], so whole sessions can be driven
+/// without a window (i.e.: in `cargo test`).
+pub struct HeadlessRenderer {
+    tile_size: f32,
+    frame: Frame,
+    inputs: VecDeque<InputEvent>,
+}
+
+impl HeadlessRenderer {
+    /// [`HeadlessRenderer::new`] creates a new [`HeadlessRenderer`] with a screen
+    /// of `columns` by `rows` tiles, which will replay `inputs` one per
+    /// [`Render::poll_input`] call.
+    pub fn new(columns: usize, rows: usize, tile_size: f32, inputs: Vec<InputEvent>) -> Self {
+        Self {
+            tile_size,
+            frame: Frame {
+                cells: CellGrid::new(columns, rows),
+                ..Default::default()
+            },
+            inputs: inputs.into(),
+        }
+    }
+
+    /// [`HeadlessRenderer::frame`] returns the [`Frame`] drawn so far.
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    /// [`HeadlessRenderer::take_frame`] returns the [`Frame`] drawn so far and
+    /// starts a new one.
+    pub fn take_frame(&mut self) -> Frame {
+        let columns = self.frame.cells.columns();
+        let rows = self.frame.cells.rows();
+        std::mem::replace(
+            &mut self.frame,
+            Frame {
+                cells: CellGrid::new(columns, rows),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// [`HeadlessRenderer::push_input`] adds an [`InputEvent`] to the end of the
+    /// scripted input queue.
+    pub fn push_input(&mut self, input: InputEvent) {
+        self.inputs.push_back(input);
+    }
+
+    /// [`HeadlessRenderer::has_input`] returns `true` if there are scripted
+    /// [`InputEvent`] left to replay.
+    pub fn has_input(&self) -> bool {
+        !self.inputs.is_empty()
+    }
+}
+
+impl Render for HeadlessRenderer {
+    fn clear_screen(&mut self) {
+        self.take_frame();
+    }
+
+    fn draw_char(&mut self, x: f32, y: f32, character: char, fg_color: Color, bg_color: Color) {
+        self.frame
+            .cells
+            .set(x.floor(), y.floor(), character, fg_color, bg_color);
+    }
+
+    fn draw_sprite(&mut self, x: f32, y: f32, sprite_id: &str) {
+        self.frame.sprites.push(SpritePlacement {
+            x,
+            y,
+            sprite_id: sprite_id.to_owned(),
+            animation: None,
+        });
+    }
+
+    fn draw_animation(&mut self, x: f32, y: f32, sheet_id: &str, animation: &str) {
+        self.frame.sprites.push(SpritePlacement {
+            x,
+            y,
+            sprite_id: sheet_id.to_owned(),
+            animation: Some(animation.to_owned()),
+        });
+    }
+
+    fn stop_animation(&mut self, _sheet_id: &str) {}
+
+    fn draw_text(&mut self, x: f32, y: f32, text: &str, fg_color: Color, bg_color: Color) {
+        // text is positioned in pixels, so convert it to a cell
+        let column = (x / self.tile_size).floor();
+        let row = (y / self.tile_size).floor();
+        for (offset, character) in text.chars().enumerate() {
+            self.frame
+                .cells
+                .set(column + offset as f32, row, character, fg_color, bg_color);
+        }
+        self.frame.texts.push(TextPlacement {
+            x,
+            y,
+            text: text.to_owned(),
+            fg_color,
+            bg_color,
+        });
+    }
+
+    fn draw_centered_text(
+        &mut self,
+        text: &str,
+        color: Color,
+        screen_width: f32,
+        screen_height: f32,
+        y_offset: f32,
+        tile_size: f32,
+    ) {
+        self.draw_text(
+            (screen_width / 2.) - (self.get_text_width(text) / 2.),
+            (screen_height / 2.) + (y_offset * tile_size),
+            text,
+            color,
+            Color::Black,
+        )
+    }
+
+    fn draw_menu(&mut self, _width: f32, _height: f32, title: &str, description: &str) {
+        let tile_size = self.tile_size;
+        self.draw_text(
+            tile_size * 2.,
+            tile_size * 2.,
+            title,
+            Color::White,
+            Color::Black,
+        );
+        self.draw_text(
+            tile_size * 3.,
+            tile_size * 4.,
+            description,
+            Color::White,
+            Color::Black,
+        );
+    }
+
+    fn poll_input(&mut self) -> Vec<InputEvent> {
+        self.inputs.pop_front().into_iter().collect()
+    }
+
+    fn get_screen_size(&self) -> (f32, f32) {
+        (
+            self.frame.cells.columns() as f32 * self.tile_size,
+            self.frame.cells.rows() as f32 * self.tile_size,
+        )
+    }
+
+    fn get_tile_size(&self) -> f32 {
+        self.tile_size
+    }
+
+    fn get_text_width(&self, text: &str) -> f32 {
+        text.chars().count() as f32 * self.tile_size
+    }
+
+    fn draw_background_effect(&mut self, effect: EffectId, params: EffectParams) {
+        self.frame.effects.push((effect, params));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{mode::Mode, screen::Screen, state::GameState};
+
+    const FRAME_TIME: f32 = 1. / 60.;
+
+    // handle input, update and draw a single frame, the same way the game loop does
+    fn run_frame(game_state: &mut GameState, renderer: &mut HeadlessRenderer) -> Frame {
+        for input in renderer.poll_input() {
+            game_state.handle_input(input, FRAME_TIME);
+        }
+        let (screen_width, screen_height) = renderer.get_screen_size();
+        let tile_size = renderer.get_tile_size();
+        game_state
+            .camera
+            .resize(screen_width / tile_size, screen_height / tile_size);
+        game_state.update(FRAME_TIME);
+        game_state.draw_screen(renderer);
+        renderer.take_frame()
+    }
+
+    #[test]
+    fn title_screen_is_drawn_and_navigated() {
+        let mut game_state = GameState::new();
+        let mut renderer = HeadlessRenderer::new(80, 40, 24., vec![InputEvent::Down]);
+
+        let before = game_state.title_menu.selected;
+        let frame = run_frame(&mut game_state, &mut renderer);
+        assert!(matches!(game_state.mode, Mode::TitleScreen));
+        assert!(frame.contains_text("New Game"));
+        assert!(frame.contains_text("Quit"));
+        assert_ne!(game_state.title_menu.selected, before);
+    }
+
+    #[test]
+    fn new_game_starts_and_the_player_walks() {
+        let mut game_state = GameState::new();
+        let mut renderer = HeadlessRenderer::new(80, 40, 24., vec![InputEvent::Interact]);
+
+        run_frame(&mut game_state, &mut renderer);
+        assert!(matches!(game_state.mode, Mode::Playing));
+        let start = (game_state.player.x, game_state.player.y);
+
+        // movement keys repeat every frame they are held
+        for _ in 0..30 {
+            renderer.push_input(InputEvent::Down);
+            run_frame(&mut game_state, &mut renderer);
+        }
+        assert_eq!(game_state.player.x, start.0);
+        assert!(game_state.player.y > start.1);
+
+        renderer.push_input(InputEvent::Down);
+        let frame = run_frame(&mut game_state, &mut renderer);
+        let walk = frame
+            .find_sprite("character_walk")
+            .expect("the walking player is drawn");
+        assert_eq!(walk.animation.as_deref(), Some("player_walk"));
+    }
+
+    #[test]
+    fn escape_pauses_over_the_world() {
+        let mut game_state = GameState::new();
+        let mut renderer =
+            HeadlessRenderer::new(80, 40, 24., vec![InputEvent::Interact, InputEvent::Quit]);
+
+        run_frame(&mut game_state, &mut renderer);
+        let frame = run_frame(&mut game_state, &mut renderer);
+        assert!(matches!(game_state.mode, Mode::Paused));
+        assert!(frame.contains_text("PAUSED"));
+        assert!(frame.effects.iter().any(|(e, _)| *e == EffectId::Dim));
+        assert!(!renderer.has_input());
+    }
+}
@@ -1,6 +1,7 @@
-use macroquad::prelude::Material;
-
-use super::color::Color;
+use super::{
+    color::Color,
+    effect::{EffectId, EffectParams},
+};
 use crate::events::input::InputEvent;
 
 pub const TILE_SIZE: f32 = 24.;
@@ -53,7 +54,8 @@ pub trait Render {
     /// [`Render::get_text_width`] returns the width of some `text` on the screen.
     fn get_text_width(&self, text: &str) -> f32;
 
-    /// [`Render::get_shader_material`] returns [`Some`] [`Material`] for a shader by
-    /// `shader_index` if one exists, and [`None`] otherwise.
-    fn get_shader_material(&self, shader_index: usize) -> Option<Material>;
+    /// [`Render::draw_background_effect`] draws the background effect denoted by
+    /// `effect` across the whole screen, using `params`. Implementations that can't
+    /// draw an effect may substitute their own or skip it.
+    fn draw_background_effect(&mut self, effect: EffectId, params: EffectParams);
 }
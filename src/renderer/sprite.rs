@@ -0,0 +1,57 @@
+use super::{color::Color, render::TILE_SIZE};
+
+/// [`CHARACTER_SPRITE_SIZE`] is the (`width`, `height`) in pixels of the standing
+/// sprite of a character, without the empty border around walk animation frames.
+pub const CHARACTER_SPRITE_SIZE: (f32, f32) = (26., 62.);
+
+/// [`CHARACTER_SIZE`] is the (`width`, `height`) in tiles of the area a character
+/// takes up, which is what they visibly cover when drawn at [`TILE_SIZE`].
+pub const CHARACTER_SIZE: (f32, f32) = (
+    CHARACTER_SPRITE_SIZE.0 / TILE_SIZE,
+    CHARACTER_SPRITE_SIZE.1 / TILE_SIZE,
+);
+
+/// [`SPRITE_GLYPHS`] is every sprite id the game knows about, along with the glyph
+/// drawn in its place by renderers that don't have art for it.
+const SPRITE_GLYPHS: &[(&str, char, Color)] = &[
+    ("player_base", '@', Color::White),
+    ("player_back", '@', Color::White),
+    ("player_left", '@', Color::White),
+    ("player_right", '@', Color::White),
+    ("player_interact", '@', Color::White),
+    ("character_walk", '@', Color::White),
+    ("villager", 'V', Color::Green),
+    ("guard", 'G', Color::Cyan),
+    ("dog", 'd', Color::Brown),
+    ("slime", 's', Color::Green),
+    ("rat", 'r', Color::Brown),
+    ("skeleton", 'S', Color::White),
+    ("remains", '%', Color::DarkGrey),
+    ("swing", ')', Color::White),
+    ("crafting_bench", '&', Color::Yellow),
+    ("title_char", '*', Color::Yellow),
+    ("icon_sword", '/', Color::White),
+    ("icon_axe", 'P', Color::White),
+    ("icon_helmet", '^', Color::Cyan),
+    ("icon_armor", '[', Color::Cyan),
+    ("icon_gloves", '"', Color::Cyan),
+    ("icon_boots", '_', Color::Cyan),
+    ("icon_wood", '=', Color::Brown),
+    ("icon_ore", '*', Color::DarkGrey),
+    ("icon_ingot", '-', Color::White),
+    ("icon_potion", '!', Color::Red),
+];
+
+/// [`is_known_sprite`] returns `true` if `sprite_id` is a sprite the game knows about.
+pub fn is_known_sprite(sprite_id: &str) -> bool {
+    SPRITE_GLYPHS.iter().any(|(id, _, _)| *id == sprite_id)
+}
+
+/// [`get_sprite_glyph`] returns the glyph and [`Color`] to draw in place of a sprite
+/// by `sprite_id`, or a red `?` if the sprite isn't known.
+pub fn get_sprite_glyph(sprite_id: &str) -> (char, Color) {
+    SPRITE_GLYPHS
+        .iter()
+        .find(|(id, _, _)| *id == sprite_id)
+        .map_or(('?', Color::Red), |(_, glyph, color)| (*glyph, *color))
+}
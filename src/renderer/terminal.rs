@@ -0,0 +1,420 @@
+use super::{
+    cell::CellGrid,
+    color,
+    effect::{EffectId, EffectParams},
+    render::Render,
+    sprite,
+};
+#[cfg(feature = "gamepad")]
+use crate::events::gamepad::GilrsGamepad;
+use crate::events::{
+    input::InputEvent,
+    input_map::{Binding, InputMap, Key, KeyboardState, Modifiers},
+    mouse,
+};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEventKind},
+    execute, queue,
+    style::{self, Print, SetBackgroundColor, SetForegroundColor},
+    terminal,
+};
+use std::{
+    io::{self, Stdout, Write},
+    time::Duration,
+};
+
+const STAR_SPACING: usize = 23;
+
+/// [`TerminalRenderer`] is an implementation of [`Render`] that draws the game as
+/// characters in a terminal, so it can be played without a window (i.e.: over SSH).
+///
+/// Every terminal cell represents one tile, and draw calls are collected into a
+/// frame buffer that is only written to the terminal by [`TerminalRenderer::present`].
+pub struct TerminalRenderer {
+    stdout: Stdout,
+    tile_size: f32,
+    cells: CellGrid,
+    previous_cells: CellGrid,
+    frame_count: u64,
+    input_map: InputMap,
+    #[cfg(feature = "gamepad")]
+    gamepad: Option<GilrsGamepad>,
+}
+
+impl TerminalRenderer {
+    /// [`TerminalRenderer::new`] takes over the terminal and creates a new
+    /// [`TerminalRenderer`], which turns key presses into input through an
+    /// [`InputMap`]. The terminal is restored when it is dropped.
+    pub fn new(tile_size: f32, input_map: InputMap) -> io::Result<Self> {
+        let mut stdout = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(
+            stdout,
+            terminal::EnterAlternateScreen,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::Hide,
+            event::EnableMouseCapture
+        )?;
+        let (columns, rows) = terminal::size()?;
+        Ok(Self {
+            stdout,
+            tile_size,
+            cells: CellGrid::new(columns as usize, rows as usize),
+            previous_cells: CellGrid::default(),
+            frame_count: 0,
+            input_map,
+            #[cfg(feature = "gamepad")]
+            gamepad: GilrsGamepad::new(),
+        })
+    }
+
+    /// [`TerminalRenderer::present`] writes every cell that changed since the last
+    /// frame to the terminal, then starts a new frame.
+    pub fn present(&mut self) -> io::Result<()> {
+        let mut last_colors = None;
+        let columns = self.cells.columns();
+        for (index, cell) in self.cells.cells().iter().enumerate() {
+            if self.previous_cells.cells().get(index) == Some(cell) {
+                continue;
+            }
+            let column = (index % columns) as u16;
+            let row = (index / columns) as u16;
+            queue!(self.stdout, cursor::MoveTo(column, row))?;
+            if last_colors != Some((cell.fg_color, cell.bg_color)) {
+                queue!(
+                    self.stdout,
+                    SetForegroundColor(map_color_to_crossterm(cell.fg_color)),
+                    SetBackgroundColor(map_color_to_crossterm(cell.bg_color))
+                )?;
+                last_colors = Some((cell.fg_color, cell.bg_color));
+            }
+            queue!(self.stdout, Print(cell.character))?;
+        }
+        queue!(self.stdout, style::ResetColor)?;
+        self.stdout.flush()?;
+
+        self.previous_cells.clone_from(&self.cells);
+        self.frame_count = self.frame_count.wrapping_add(1);
+        self.clear_screen();
+        Ok(())
+    }
+
+    fn resize(&mut self, columns: u16, rows: u16) {
+        self.cells = CellGrid::new(columns as usize, rows as usize);
+        // force a full redraw on the next frame
+        self.previous_cells = CellGrid::default();
+        let _ = execute!(self.stdout, terminal::Clear(terminal::ClearType::All));
+    }
+
+    fn map_key_to_input(&self, key: KeyEvent) -> Vec<InputEvent> {
+        // any key pressed, ahead of what it is bound to, so it can be rebound
+        let keyboard = TerminalKeyboard::from_key_event(key);
+        let mut events: Vec<InputEvent> = Binding::get_pressed(&keyboard)
+            .map(InputEvent::KeyPressed)
+            .into_iter()
+            .collect();
+        events.extend(self.input_map.map_input(&keyboard));
+
+        // raw mode swallows the interrupt signal, so treat ctrl+c like escape
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            events.push(InputEvent::Quit)
+        }
+
+        // general keyboard character input
+        if let KeyCode::Char(c) = key.code
+            && c.is_alphanumeric()
+            && !key.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            events.push(InputEvent::Character(c.to_ascii_lowercase()));
+        }
+
+        events
+    }
+}
+
+// a terminal only reports key presses, so a key is down for the one event it was pressed in
+struct TerminalKeyboard {
+    key: Option<Key>,
+    modifiers: Modifiers,
+}
+
+impl TerminalKeyboard {
+    fn from_key_event(key_event: KeyEvent) -> Self {
+        let key = match key_event.code {
+            KeyCode::Up => Some(Key::Up),
+            KeyCode::Down => Some(Key::Down),
+            KeyCode::Left => Some(Key::Left),
+            KeyCode::Right => Some(Key::Right),
+            KeyCode::Enter => Some(Key::Enter),
+            KeyCode::Esc => Some(Key::Escape),
+            KeyCode::Tab | KeyCode::BackTab => Some(Key::Tab),
+            KeyCode::Backspace => Some(Key::Backspace),
+            KeyCode::Char(c) => Key::from_char(c),
+            _ => None,
+        };
+        let modifiers = Modifiers {
+            // shift+tab arrives as its own key
+            shift: key_event.modifiers.contains(KeyModifiers::SHIFT)
+                || key_event.code == KeyCode::BackTab,
+            ctrl: key_event.modifiers.contains(KeyModifiers::CONTROL),
+            alt: key_event.modifiers.contains(KeyModifiers::ALT),
+        };
+        Self { key, modifiers }
+    }
+}
+
+impl KeyboardState for TerminalKeyboard {
+    fn is_key_down(&self, key: Key) -> bool {
+        self.key == Some(key)
+    }
+
+    fn is_key_pressed(&self, key: Key) -> bool {
+        self.key == Some(key)
+    }
+
+    fn get_modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+}
+
+impl Drop for TerminalRenderer {
+    fn drop(&mut self) {
+        let _ = execute!(
+            self.stdout,
+            style::ResetColor,
+            event::DisableMouseCapture,
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+impl Render for TerminalRenderer {
+    fn clear_screen(&mut self) {
+        self.cells.clear();
+    }
+
+    fn draw_char(
+        &mut self,
+        x: f32,
+        y: f32,
+        character: char,
+        fg_color: color::Color,
+        bg_color: color::Color,
+    ) {
+        self.cells
+            .set(x.floor(), y.floor(), character, fg_color, bg_color);
+    }
+
+    // draw a glyph in place of the sprite based on `sprite_id`
+    fn draw_sprite(&mut self, x: f32, y: f32, sprite_id: &str) {
+        let (character, fg_color) = sprite::get_sprite_glyph(sprite_id);
+        self.draw_char(x, y, character, fg_color, color::Color::Transparent);
+    }
+
+    fn draw_animation(&mut self, x: f32, y: f32, sheet_id: &str, _animation: &str) {
+        self.draw_sprite(x, y, sheet_id);
+    }
+
+    // glyphs are not animated, so there is nothing to stop
+    fn stop_animation(&mut self, _sheet_id: &str) {}
+
+    fn draw_text(
+        &mut self,
+        x: f32,
+        y: f32,
+        text: &str,
+        fg_color: color::Color,
+        bg_color: color::Color,
+    ) {
+        // text is positioned in pixels, so convert it to a cell
+        let column = (x / self.tile_size).floor();
+        let row = (y / self.tile_size).floor();
+        for (offset, character) in text.chars().enumerate() {
+            self.cells
+                .set(column + offset as f32, row, character, fg_color, bg_color);
+        }
+    }
+
+    fn draw_centered_text(
+        &mut self,
+        text: &str,
+        color: color::Color,
+        screen_width: f32,
+        screen_height: f32,
+        y_offset: f32,
+        tile_size: f32,
+    ) {
+        self.draw_text(
+            (screen_width / 2.) - (self.get_text_width(text) / 2.),
+            (screen_height / 2.) + (y_offset * tile_size),
+            text,
+            color,
+            color::Color::Black,
+        )
+    }
+
+    fn draw_menu(&mut self, width: f32, height: f32, title: &str, description: &str) {
+        let (left, top) = (2., 2.);
+        let right = left + width - 1.;
+        let bottom = top + height - 1.;
+        let (fg, bg) = (color::Color::White, color::Color::Black);
+        for x in 0..width as usize {
+            for y in 0..height as usize {
+                let (column, row) = (left + x as f32, top + y as f32);
+                let border = match (
+                    column == left || column == right,
+                    row == top || row == bottom,
+                ) {
+                    (true, true) => '+',
+                    (true, false) => '|',
+                    (false, true) => '-',
+                    (false, false) => ' ',
+                };
+                self.cells.set(column, row, border, fg, bg);
+            }
+        }
+        let tile_size = self.tile_size;
+        self.draw_text(
+            (left + 2.) * tile_size,
+            top * tile_size,
+            &format!(" {title} "),
+            color::Color::Yellow,
+            bg,
+        );
+        self.draw_text(
+            (left + 2.) * tile_size,
+            (top + 2.) * tile_size,
+            description,
+            fg,
+            bg,
+        );
+        self.draw_text(
+            (left + 2.) * tile_size,
+            (bottom - 1.) * tile_size,
+            "[close]",
+            color::Color::DarkGrey,
+            bg,
+        );
+    }
+
+    fn get_screen_size(&self) -> (f32, f32) {
+        (
+            self.cells.columns() as f32 * self.tile_size,
+            self.cells.rows() as f32 * self.tile_size,
+        )
+    }
+
+    fn get_tile_size(&self) -> f32 {
+        self.tile_size
+    }
+
+    fn get_text_width(&self, text: &str) -> f32 {
+        text.chars().count() as f32 * self.tile_size
+    }
+
+    fn poll_input(&mut self) -> Vec<InputEvent> {
+        let mut events: Vec<InputEvent> = Vec::new();
+        while let Ok(true) = event::poll(Duration::ZERO) {
+            let Ok(terminal_event) = event::read() else {
+                break;
+            };
+            match terminal_event {
+                Event::Key(key) if key.kind != KeyEventKind::Release => {
+                    events.extend(self.map_key_to_input(key));
+                }
+                Event::Mouse(mouse_event) => {
+                    let (x, y) = (mouse_event.column as f32, mouse_event.row as f32);
+                    let button = match mouse_event.kind {
+                        MouseEventKind::Down(event::MouseButton::Left) => mouse::MouseButton::Left,
+                        MouseEventKind::Down(event::MouseButton::Right) => {
+                            mouse::MouseButton::Right
+                        }
+                        MouseEventKind::Down(event::MouseButton::Middle) => {
+                            mouse::MouseButton::Middle
+                        }
+                        MouseEventKind::Moved => {
+                            events.push(InputEvent::MouseMove { x, y });
+                            continue;
+                        }
+                        _ => continue,
+                    };
+                    events.push(InputEvent::MouseClick { x, y, button });
+                }
+                Event::Resize(columns, rows) => self.resize(columns, rows),
+                _ => {}
+            }
+        }
+
+        // gamepad input
+        #[cfg(feature = "gamepad")]
+        if let Some(gamepad) = &mut self.gamepad {
+            events.extend(self.input_map.map_gamepad(&gamepad.poll()));
+        }
+
+        events
+    }
+
+    // shaders can't be drawn in a terminal, so substitute a field of twinkling glyphs
+    fn draw_background_effect(&mut self, effect: EffectId, _params: EffectParams) {
+        match effect {
+            EffectId::Starfield => {
+                for row in 0..self.cells.rows() {
+                    for column in 0..self.cells.columns() {
+                        let seed = (column * 31 + row * 17) ^ (column * row);
+                        if !seed.is_multiple_of(STAR_SPACING) {
+                            continue;
+                        }
+                        let (star, star_color) =
+                            if (seed as u64 + self.frame_count / 8).is_multiple_of(3) {
+                                ('*', color::Color::White)
+                            } else {
+                                ('.', color::Color::DarkGrey)
+                            };
+                        self.cells.set(
+                            column as f32,
+                            row as f32,
+                            star,
+                            star_color,
+                            color::Color::Black,
+                        );
+                    }
+                }
+            }
+            EffectId::Dim => {
+                // grey out every character, since a terminal can't draw anything see-through
+                for row in 0..self.cells.rows() {
+                    for column in 0..self.cells.columns() {
+                        if let Some(cell) = self.cells.get(column, row) {
+                            self.cells.set(
+                                column as f32,
+                                row as f32,
+                                cell.character,
+                                color::Color::DarkGrey,
+                                color::Color::Black,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn map_color_to_crossterm(color: color::Color) -> style::Color {
+    match color {
+        color::Color::Black => style::Color::Black,
+        color::Color::White => style::Color::White,
+        color::Color::Red => style::Color::Red,
+        color::Color::Green => style::Color::DarkGreen,
+        color::Color::Blue => style::Color::DarkBlue,
+        color::Color::Yellow => style::Color::DarkYellow,
+        color::Color::Cyan => style::Color::Cyan,
+        color::Color::Magenta => style::Color::Magenta,
+        color::Color::Brown => style::Color::DarkRed,
+        color::Color::DarkGrey => style::Color::DarkGrey,
+        color::Color::Transparent => style::Color::Reset,
+    }
+}
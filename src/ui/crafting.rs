@@ -1,10 +1,153 @@
-use super::status_bar;
-use crate::{game::mode::Mode, maps::map::Map, renderer::render::Render};
-
-/// [`draw_crafting_screen`] will draw the crafting menu on top of the [`Map`]
-/// using a [`Render`] implementation.
-pub fn draw_crafting_screen<R: Render>(map: &Map, renderer: &mut R) {
-    map.draw_map(renderer);
-    renderer.draw_menu(20., 20., "CRAFTING", "This will be where you craft stuff.");
-    status_bar::draw_status_bar(Mode::Crafting, "Press ESC to resume", renderer);
+use super::{
+    panel::{draw_box, text_y},
+    status_bar,
+};
+use crate::{
+    events::input_map::{Action, InputMap},
+    game::{crafting::CraftingMenu, mode::Mode, player::Player},
+    items::{catalog::ItemCatalog, recipe::RecipeBook},
+    renderer::{color::Color, render::Render},
+};
+
+const LIST_LEFT: f32 = 2.;
+const LIST_TOP: f32 = 4.;
+const LIST_WIDTH: f32 = 26.;
+const DETAILS_LEFT: f32 = LIST_LEFT + LIST_WIDTH + 2.;
+const PANEL_WIDTH: f32 = 62.;
+
+/// [`draw_crafting_screen`] will draw the crafting menu listing every recipe of a
+/// [`RecipeBook`] on top of the world using a [`Render`] implementation. Keys are
+/// named as bound in the [`InputMap`].
+pub fn draw_crafting_screen<R: Render>(
+    player: &Player,
+    recipes: &RecipeBook,
+    items: &ItemCatalog,
+    menu: &CraftingMenu,
+    input_map: &InputMap,
+    renderer: &mut R,
+) {
+    let tile_size = renderer.get_tile_size();
+    let recipe_count = recipes.recipes().len() as f32;
+    let panel_height = recipe_count.max(8.) + 7.;
+    draw_box(
+        LIST_LEFT - 1.,
+        LIST_TOP - 3.,
+        PANEL_WIDTH,
+        panel_height,
+        Color::Black,
+        renderer,
+    );
+    let title = match menu.station {
+        Some(station) => format!("CRAFTING - {station}"),
+        None => "CRAFTING".to_owned(),
+    };
+    renderer.draw_text(
+        LIST_LEFT * tile_size,
+        text_y(LIST_TOP - 2., tile_size),
+        &title,
+        Color::Yellow,
+        Color::Black,
+    );
+
+    for (index, recipe) in recipes.recipes().iter().enumerate() {
+        let row = LIST_TOP + index as f32;
+        let is_selected = index == menu.selected;
+        let bg_color = if is_selected {
+            Color::Blue
+        } else {
+            Color::Black
+        };
+        let fg_color = if recipe.can_craft(&player.inventory, menu.station) {
+            Color::Green
+        } else {
+            Color::DarkGrey
+        };
+        draw_box(LIST_LEFT, row, LIST_WIDTH, 1., bg_color, renderer);
+        renderer.draw_text(
+            LIST_LEFT * tile_size,
+            text_y(row, tile_size),
+            &format!("{} {}", if is_selected { ">" } else { " " }, recipe.name),
+            fg_color,
+            bg_color,
+        );
+    }
+
+    if let Some(recipe) = recipes.recipes().get(menu.selected) {
+        let mut lines: Vec<(String, Color)> = vec![("Needs:".to_owned(), Color::White)];
+        for input in &recipe.inputs {
+            let held = player.inventory.count(&input.item_id);
+            lines.push((
+                format!(
+                    "  {}x {} ({held}/{})",
+                    input.quantity,
+                    get_item_name(items, &input.item_id),
+                    input.quantity
+                ),
+                if held >= input.quantity {
+                    Color::Green
+                } else {
+                    Color::Red
+                },
+            ));
+        }
+        lines.push(("Makes:".to_owned(), Color::White));
+        for output in &recipe.outputs {
+            lines.push((
+                format!(
+                    "  {}x {}",
+                    output.quantity,
+                    get_item_name(items, &output.item_id)
+                ),
+                Color::Yellow,
+            ));
+        }
+        if let Some(building) = recipe.building
+            && !recipe.is_available_at(menu.station)
+        {
+            lines.push((format!("Requires a {building}"), Color::Red));
+        }
+        for (offset, (line, color)) in lines.iter().enumerate() {
+            renderer.draw_text(
+                DETAILS_LEFT * tile_size,
+                text_y(LIST_TOP + offset as f32, tile_size),
+                line,
+                *color,
+                Color::Black,
+            );
+        }
+    }
+
+    if let Some(message) = &menu.message {
+        renderer.draw_text(
+            LIST_LEFT * tile_size,
+            text_y(LIST_TOP + panel_height - 5., tile_size),
+            message,
+            Color::Yellow,
+            Color::Black,
+        );
+    }
+
+    status_bar::draw_status_bar(
+        Mode::Crafting,
+        &format!(
+            "Press {} to craft, {} to resume",
+            input_map.describe(Action::Interact),
+            input_map.describe(Action::Pause)
+        ),
+        renderer,
+    );
+}
+
+/// [`get_recipe_at`] returns [`Some`] index of a recipe in a list of `recipe_count`
+/// recipes at a tile (`x`, `y`) coordinate, and [`None`] otherwise.
+pub fn get_recipe_at(x: f32, y: f32, recipe_count: usize) -> Option<usize> {
+    let index = (y - LIST_TOP).floor();
+    let within_list = (LIST_LEFT..LIST_LEFT + LIST_WIDTH).contains(&x) && index >= 0.;
+    (within_list && (index as usize) < recipe_count).then_some(index as usize)
+}
+
+fn get_item_name<'a>(items: &'a ItemCatalog, item_id: &'a str) -> &'a str {
+    items
+        .get(item_id)
+        .map_or(item_id, |item| item.name.as_str())
 }
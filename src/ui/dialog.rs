@@ -0,0 +1,144 @@
+use super::{
+    panel::{draw_box, text_y},
+    status_bar,
+};
+use crate::{
+    events::input_map::{Action, InputMap},
+    game::{
+        dialog::{Conversation, DialogBook},
+        mode::Mode,
+        player::Player,
+    },
+    renderer::{color::Color, render::Render},
+};
+
+const DIALOG_LEFT: f32 = 2.;
+const DIALOG_TOP: f32 = 3.;
+const DIALOG_WIDTH: f32 = 48.;
+
+/// [`draw_dialog_screen`] will draw the speaker, text and choices of a
+/// [`Conversation`] from a [`DialogBook`] on top of the world using a [`Render`]
+/// implementation. The text is typed out, and only the choices a [`Player`] can pick
+/// with the `flags` that have been set are shown once it is done. The status bar
+/// shows [`Some`] `notification` about what the dialog gave or took, and keys are
+/// named as bound in the [`InputMap`].
+pub fn draw_dialog_screen<R: Render>(
+    player: &Player,
+    dialogs: &DialogBook,
+    conversation: &Conversation,
+    flags: &[String],
+    notification: Option<&str>,
+    input_map: &InputMap,
+    renderer: &mut R,
+) {
+    let Some(dialog) = dialogs.get(&conversation.dialog_id) else {
+        return;
+    };
+    let Some(node) = dialog.get_node(&conversation.node_id) else {
+        return;
+    };
+    let tile_size = renderer.get_tile_size();
+    let lines = wrap_text(&node.text, DIALOG_WIDTH as usize);
+    let choices = node.get_choices(player, flags);
+    let is_revealed = conversation.is_revealed(&node.text);
+    let choices_top = get_choices_top(&node.text);
+    draw_box(
+        DIALOG_LEFT - 1.,
+        DIALOG_TOP - 2.,
+        DIALOG_WIDTH + 2.,
+        choices_top - DIALOG_TOP + choices.len() as f32 + 3.,
+        Color::Black,
+        renderer,
+    );
+    renderer.draw_text(
+        DIALOG_LEFT * tile_size,
+        text_y(DIALOG_TOP - 1., tile_size),
+        &dialog.speaker,
+        Color::Yellow,
+        Color::Black,
+    );
+
+    // the revealed characters are typed out across the lines in order
+    let mut revealed = conversation.get_revealed();
+    for (row, line) in lines.iter().enumerate() {
+        let shown: String = line.chars().take(revealed).collect();
+        revealed = revealed.saturating_sub(line.chars().count());
+        renderer.draw_text(
+            DIALOG_LEFT * tile_size,
+            text_y(DIALOG_TOP + row as f32, tile_size),
+            &shown,
+            Color::White,
+            Color::Black,
+        );
+    }
+
+    if is_revealed {
+        for (index, choice) in choices.iter().enumerate() {
+            let row = choices_top + index as f32;
+            let is_selected = index == conversation.selected;
+            let bg_color = if is_selected {
+                Color::Blue
+            } else {
+                Color::Black
+            };
+            draw_box(DIALOG_LEFT, row, DIALOG_WIDTH, 1., bg_color, renderer);
+            renderer.draw_text(
+                DIALOG_LEFT * tile_size,
+                text_y(row, tile_size),
+                &format!(
+                    "{} {}. {}",
+                    if is_selected { ">" } else { " " },
+                    index + 1,
+                    choice.text
+                ),
+                Color::Green,
+                bg_color,
+            );
+        }
+    }
+
+    let verb = if !is_revealed {
+        "skip"
+    } else if choices.is_empty() {
+        "continue"
+    } else {
+        "choose"
+    };
+    let status = format!(
+        "Press {} to {verb}, {} to leave",
+        input_map.describe(Action::Interact),
+        input_map.describe(Action::Pause)
+    );
+    status_bar::draw_status_bar(Mode::Dialog, notification.unwrap_or(&status), renderer);
+}
+
+/// [`get_choice_at`] returns [`Some`] index of a choice in a list of `choice_count`
+/// choices shown below a `text` at a tile (`x`, `y`) coordinate, and [`None`]
+/// otherwise.
+pub fn get_choice_at(x: f32, y: f32, text: &str, choice_count: usize) -> Option<usize> {
+    let index = (y - get_choices_top(text)).floor();
+    let within_list = (DIALOG_LEFT..DIALOG_LEFT + DIALOG_WIDTH).contains(&x) && index >= 0.;
+    (within_list && (index as usize) < choice_count).then_some(index as usize)
+}
+
+// the choices start a row below the wrapped text
+fn get_choices_top(text: &str) -> f32 {
+    DIALOG_TOP + wrap_text(text, DIALOG_WIDTH as usize).len() as f32 + 1.
+}
+
+// split text into lines of at most `width` characters, breaking between words
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines
+}
@@ -0,0 +1,98 @@
+use super::{
+    panel::{draw_box, text_y},
+    status_bar,
+};
+use crate::{
+    events::input_map::{Action, InputMap},
+    game::{
+        game_over::{GameOverMenu, GameOverOption, RespawnPenalty},
+        mode::Mode,
+    },
+    renderer::{color::Color, render::Render},
+};
+
+const MENU_LEFT: f32 = 4.;
+const MENU_TOP: f32 = 6.;
+const MENU_WIDTH: f32 = 44.;
+
+/// [`draw_game_over_screen`] will draw the options of a [`GameOverMenu`] on top of
+/// the world using a [`Render`] implementation. Loading is only offered if there
+/// `has_save`, respawning describes the [`RespawnPenalty`], and the status bar shows
+/// [`Some`] `notification` about the last choice. Keys are named as bound in the
+/// [`InputMap`].
+pub fn draw_game_over_screen<R: Render>(
+    menu: &GameOverMenu,
+    has_save: bool,
+    penalty: &RespawnPenalty,
+    notification: Option<&str>,
+    input_map: &InputMap,
+    renderer: &mut R,
+) {
+    let tile_size = renderer.get_tile_size();
+    draw_box(
+        MENU_LEFT - 1.,
+        MENU_TOP - 3.,
+        MENU_WIDTH + 2.,
+        GameOverOption::ALL.len() as f32 + 5.,
+        Color::Black,
+        renderer,
+    );
+    renderer.draw_text(
+        MENU_LEFT * tile_size,
+        text_y(MENU_TOP - 2., tile_size),
+        "YOU HAVE FALLEN",
+        Color::Red,
+        Color::Black,
+    );
+
+    for (index, option) in GameOverOption::ALL.iter().enumerate() {
+        let row = MENU_TOP + index as f32;
+        let is_selected = index == menu.selected;
+        let bg_color = if is_selected {
+            Color::Blue
+        } else {
+            Color::Black
+        };
+        let fg_color = if *option == GameOverOption::LoadSave && !has_save {
+            Color::DarkGrey
+        } else {
+            Color::White
+        };
+        let label = match option {
+            GameOverOption::Respawn => format!("{option} ({})", get_penalty_text(penalty)),
+            _ => option.to_string(),
+        };
+        draw_box(MENU_LEFT, row, MENU_WIDTH, 1., bg_color, renderer);
+        renderer.draw_text(
+            MENU_LEFT * tile_size,
+            text_y(row, tile_size),
+            &format!("{} {label}", if is_selected { ">" } else { " " }),
+            fg_color,
+            bg_color,
+        );
+    }
+
+    let status = format!("Press {} to choose", input_map.describe(Action::Interact));
+    status_bar::draw_status_bar(Mode::GameOver, notification.unwrap_or(&status), renderer);
+}
+
+/// [`get_option_at`] returns [`Some`] index of a [`GameOverOption`] at a tile (`x`,
+/// `y`) coordinate, and [`None`] otherwise.
+pub fn get_option_at(x: f32, y: f32) -> Option<usize> {
+    let index = (y - MENU_TOP).floor();
+    let within_list = (MENU_LEFT..MENU_LEFT + MENU_WIDTH).contains(&x) && index >= 0.;
+    (within_list && (index as usize) < GameOverOption::ALL.len()).then_some(index as usize)
+}
+
+// what respawning costs, in a few words
+fn get_penalty_text(penalty: &RespawnPenalty) -> String {
+    let money = format!(
+        "lose {}% of your money",
+        (penalty.money_lost * 100.).round()
+    );
+    if penalty.drop_items {
+        format!("{money} and drop your items")
+    } else {
+        money
+    }
+}
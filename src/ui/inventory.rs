@@ -0,0 +1,187 @@
+use super::{
+    panel::{draw_box, text_y},
+    status_bar,
+};
+use crate::{
+    events::input_map::{Action, InputMap},
+    game::{mode::Mode, player::Player},
+    items::equipment::EquipSlot,
+    renderer::{color::Color, render::Render},
+};
+
+/// [`GRID_COLUMNS`] is how many slots are drawn in each row of the inventory grid.
+pub const GRID_COLUMNS: usize = 5;
+const GRID_LEFT: f32 = 2.;
+const GRID_TOP: f32 = 4.;
+const SLOT_WIDTH: f32 = 10.;
+const SLOT_HEIGHT: f32 = 3.;
+
+/// [`draw_inventory_screen`] will draw the [`Player`] inventory as a grid, and their
+/// equipment below it, on top of the world using a [`Render`] implementation. The
+/// status bar shows [`Some`] `notification` about the last equip attempt. Keys are
+/// named as bound in the [`InputMap`].
+pub fn draw_inventory_screen<R: Render>(
+    player: &Player,
+    notification: Option<&str>,
+    input_map: &InputMap,
+    renderer: &mut R,
+) {
+    let tile_size = renderer.get_tile_size();
+    let inventory = &player.inventory;
+    let rows = inventory.capacity().div_ceil(GRID_COLUMNS) as f32;
+    let panel_width = GRID_COLUMNS as f32 * SLOT_WIDTH + 2.;
+    let panel_height = rows * SLOT_HEIGHT + 8. + EquipSlot::ALL.len() as f32;
+    draw_box(
+        GRID_LEFT - 1.,
+        GRID_TOP - 3.,
+        panel_width,
+        panel_height,
+        Color::Black,
+        renderer,
+    );
+    renderer.draw_text(
+        GRID_LEFT * tile_size,
+        text_y(GRID_TOP - 2., tile_size),
+        "INVENTORY",
+        Color::Yellow,
+        Color::Black,
+    );
+
+    for slot in 0..inventory.capacity() {
+        let (left, top) = get_slot_position(slot);
+        let bg_color = if slot == inventory.selected {
+            Color::Blue
+        } else {
+            Color::DarkGrey
+        };
+        draw_box(
+            left,
+            top,
+            SLOT_WIDTH - 1.,
+            SLOT_HEIGHT - 1.,
+            bg_color,
+            renderer,
+        );
+        if let Some(stack) = inventory.stacks().get(slot) {
+            let name: String = stack
+                .item
+                .name
+                .chars()
+                .take(SLOT_WIDTH as usize - 2)
+                .collect();
+            renderer.draw_text(
+                left * tile_size,
+                text_y(top, tile_size),
+                &name,
+                stack.item.rarity.get_color(),
+                bg_color,
+            );
+            renderer.draw_text(
+                left * tile_size,
+                text_y(top + 1., tile_size),
+                &format!("x{}", stack.quantity),
+                Color::Yellow,
+                bg_color,
+            );
+        }
+    }
+
+    let details_top = GRID_TOP + rows * SLOT_HEIGHT;
+    if let Some(stack) = inventory.get_selected_stack() {
+        let item = &stack.item;
+        let mut stats: Vec<String> = item.properties.iter().map(|p| p.to_string()).collect();
+        stats.push(format!("Weight {}", item.weight));
+        stats.push(format!("Value {}", item.get_price()));
+        let lines = [
+            (
+                format!(
+                    "{} x{} ({} {:?})",
+                    item.name, stack.quantity, item.rarity, item.item_type
+                ),
+                item.rarity.get_color(),
+            ),
+            (item.description.clone(), Color::White),
+            (stats.join(" | "), Color::DarkGrey),
+        ];
+        for (offset, (line, color)) in lines.iter().enumerate() {
+            renderer.draw_text(
+                GRID_LEFT * tile_size,
+                text_y(details_top + offset as f32, tile_size),
+                line,
+                *color,
+                Color::Black,
+            );
+        }
+    } else {
+        renderer.draw_text(
+            GRID_LEFT * tile_size,
+            text_y(details_top, tile_size),
+            "Empty slot",
+            Color::DarkGrey,
+            Color::Black,
+        );
+    }
+
+    let equipment_top = get_equipment_top(inventory.capacity());
+    renderer.draw_text(
+        GRID_LEFT * tile_size,
+        text_y(equipment_top - 1., tile_size),
+        &format!(
+            "EQUIPMENT (Damage {} | Armor {})",
+            player.damage, player.armor_rating
+        ),
+        Color::Yellow,
+        Color::Black,
+    );
+    for (row, slot) in EquipSlot::ALL.iter().enumerate() {
+        let (name, color) = match player.equipment.get(*slot) {
+            Some(item) => (item.name.as_str(), item.rarity.get_color()),
+            None => ("-", Color::DarkGrey),
+        };
+        renderer.draw_text(
+            GRID_LEFT * tile_size,
+            text_y(equipment_top + row as f32, tile_size),
+            &format!("{:<7}{name}", slot.to_string()),
+            color,
+            Color::Black,
+        );
+    }
+
+    let status = format!(
+        "{} to equip, click gear to unequip, {} to resume",
+        input_map.describe(Action::Interact),
+        input_map.describe(Action::Pause)
+    );
+    status_bar::draw_status_bar(Mode::Inventory, notification.unwrap_or(&status), renderer);
+}
+
+/// [`get_slot_at`] returns [`Some`] slot index of an inventory with `capacity`
+/// slots at a tile (`x`, `y`) coordinate, and [`None`] otherwise.
+pub fn get_slot_at(x: f32, y: f32, capacity: usize) -> Option<usize> {
+    (0..capacity).find(|slot| {
+        let (left, top) = get_slot_position(*slot);
+        x >= left && x < left + SLOT_WIDTH - 1. && y >= top && y < top + SLOT_HEIGHT - 1.
+    })
+}
+
+/// [`get_equip_slot_at`] returns [`Some`] [`EquipSlot`] drawn at a tile (`x`, `y`)
+/// coordinate below an inventory with `capacity` slots, and [`None`] otherwise.
+pub fn get_equip_slot_at(x: f32, y: f32, capacity: usize) -> Option<EquipSlot> {
+    let row = y - get_equipment_top(capacity);
+    if x < GRID_LEFT || x >= GRID_LEFT + GRID_COLUMNS as f32 * SLOT_WIDTH || row < 0. {
+        return None;
+    }
+    EquipSlot::ALL.get(row as usize).copied()
+}
+
+// the equipment list starts below the item details
+fn get_equipment_top(capacity: usize) -> f32 {
+    GRID_TOP + capacity.div_ceil(GRID_COLUMNS) as f32 * SLOT_HEIGHT + 4.
+}
+
+fn get_slot_position(slot: usize) -> (f32, f32) {
+    (
+        GRID_LEFT + (slot % GRID_COLUMNS) as f32 * SLOT_WIDTH,
+        GRID_TOP + (slot / GRID_COLUMNS) as f32 * SLOT_HEIGHT,
+    )
+}
@@ -1,52 +1,110 @@
-use super::status_bar;
-use crate::{
-    game::mode::Mode,
-    renderer::{color::Color, render::Render},
+use super::{
+    panel::{draw_box, text_y},
+    status_bar,
 };
-use macroquad::{
-    color::WHITE,
-    math::vec2,
-    prelude::{Material, gl_use_default_material, gl_use_material},
-    texture::{DrawTextureParams, FilterMode, draw_texture_ex, render_target},
+use crate::{
+    events::input_map::{Action, InputMap},
+    game::{
+        mode::Mode,
+        title_menu::{TitleMenu, TitleOption},
+    },
+    renderer::{
+        color::Color,
+        effect::{EffectId, EffectParams},
+        render::Render,
+    },
 };
 
 const TITLE: &str = "RUST & RUIN";
 const TITLE_LINE: &str = "===========";
-const INSTRUCTIONS: &str = "Press Enter to Start";
-const QUIT_INFO: &str = "Press ESC to Quit";
+const CREDITS: [&str; 4] = [
+    "Made by the Rust & Ruin contributors",
+    "Built with Rust and macroquad",
+    "",
+    "Thanks for playing!",
+];
+const MENU_WIDTH: f32 = 16.;
+// how many rows below the middle of the screen the menu starts
+const MENU_OFFSET: f32 = 1.;
 
-/// [`draw_title_screen`] will draw the title screen using a [`Render`] implementation.
-pub fn draw_title_screen<R: Render>(renderer: &mut R) {
+/// [`draw_title_screen`] will draw the title screen and the entries of a
+/// [`TitleMenu`] using a [`Render`] implementation, or the credits while they are
+/// shown. Entries that aren't enabled with or without a save are greyed out, and a
+/// `message` is shown below the menu if there is one. Keys are named as bound in the
+/// [`InputMap`].
+pub fn draw_title_screen<R: Render>(
+    menu: &TitleMenu,
+    has_save: bool,
+    message: Option<&str>,
+    input_map: &InputMap,
+    renderer: &mut R,
+) {
     let (w, h) = renderer.get_screen_size();
     let tile_size = renderer.get_tile_size();
-    let starfield_shader_material = renderer.get_shader_material(0).unwrap();
-    draw_starfield_shader(starfield_shader_material, w, h);
+    renderer.draw_background_effect(EffectId::Starfield, EffectParams::default());
     renderer.draw_centered_text(TITLE, Color::White, w, h, -2., tile_size);
     renderer.draw_centered_text(TITLE_LINE, Color::Yellow, w, h, -1., tile_size);
-    renderer.draw_centered_text(INSTRUCTIONS, Color::Green, w, h, 2., tile_size);
-    renderer.draw_centered_text(QUIT_INFO, Color::DarkGrey, w, h, 4., tile_size);
     draw_decorations(w, h, tile_size, renderer);
 
+    if menu.is_showing_credits {
+        for (index, line) in CREDITS.iter().enumerate() {
+            let y_offset = MENU_OFFSET + 1. + index as f32;
+            renderer.draw_centered_text(line, Color::Cyan, w, h, y_offset, tile_size);
+        }
+        let status = format!("Press {} to go back", input_map.describe(Action::Interact));
+        status_bar::draw_status_bar(Mode::TitleScreen, &status, renderer);
+        return;
+    }
+
+    let (left, top) = get_menu_origin(w / tile_size, h / tile_size);
+    for (index, option) in TitleOption::ALL.iter().enumerate() {
+        let row = top + index as f32;
+        let is_selected = index == menu.selected;
+        let bg_color = if is_selected {
+            Color::Blue
+        } else {
+            Color::Black
+        };
+        let fg_color = if !option.is_enabled(has_save) {
+            Color::DarkGrey
+        } else if is_selected {
+            Color::Yellow
+        } else {
+            Color::White
+        };
+        draw_box(left, row, MENU_WIDTH, 1., bg_color, renderer);
+        renderer.draw_text(
+            left * tile_size,
+            text_y(row, tile_size),
+            &format!("{} {option}", if is_selected { ">" } else { " " }),
+            fg_color,
+            bg_color,
+        );
+    }
+    if let Some(message) = message {
+        let y_offset = MENU_OFFSET + TitleOption::ALL.len() as f32 + 2.;
+        renderer.draw_centered_text(message, Color::Red, w, h, y_offset, tile_size);
+    }
+
     status_bar::draw_status_bar(Mode::TitleScreen, "pre-alpha", renderer);
 }
 
-fn draw_starfield_shader(material: Material, screen_w: f32, screen_h: f32) {
-    let render_target = render_target(320, 150);
-    render_target.texture.set_filter(FilterMode::Nearest);
-    material.set_uniform("iResolution", (screen_w, screen_h));
-    material.set_uniform("direction_modifier", 0f32);
-    gl_use_material(material);
-    draw_texture_ex(
-        render_target.texture,
-        0.,
-        0.,
-        WHITE,
-        DrawTextureParams {
-            dest_size: Some(vec2(screen_w, screen_h)),
-            ..Default::default()
-        },
-    );
-    gl_use_default_material();
+/// [`get_title_option_at`] returns [`Some`] index of a [`TitleOption`] at a tile (`x`,
+/// `y`) coordinate on a screen `columns` by `rows` tiles in size, and [`None`]
+/// otherwise.
+pub fn get_title_option_at(x: f32, y: f32, columns: f32, rows: f32) -> Option<usize> {
+    let (left, top) = get_menu_origin(columns, rows);
+    let index = (y - top).floor();
+    let within_list = (left..left + MENU_WIDTH).contains(&x) && index >= 0.;
+    (within_list && (index as usize) < TitleOption::ALL.len()).then_some(index as usize)
+}
+
+// the menu is centered across the screen, starting just below the title
+fn get_menu_origin(columns: f32, rows: f32) -> (f32, f32) {
+    (
+        ((columns - MENU_WIDTH) / 2.).floor(),
+        (rows / 2.).floor() + MENU_OFFSET,
+    )
 }
 
 fn draw_decorations<R: Render>(screen_w: f32, screen_h: f32, tile_size: f32, renderer: &mut R) {
@@ -0,0 +1,30 @@
+use crate::renderer::{color::Color, render::Render};
+
+/// [`text_y`] returns the pixel position to draw text at so it sits in a tile `row`,
+/// since text is drawn from its baseline.
+pub fn text_y(row: f32, tile_size: f32) -> f32 {
+    (row + 0.75) * tile_size
+}
+
+/// [`draw_box`] fills a `width` by `height` area of tiles starting at (`left`, `top`)
+/// with a `bg_color` using a [`Render`] implementation.
+pub fn draw_box<R: Render>(
+    left: f32,
+    top: f32,
+    width: f32,
+    height: f32,
+    bg_color: Color,
+    renderer: &mut R,
+) {
+    for x in 0..width as usize {
+        for y in 0..height as usize {
+            renderer.draw_char(
+                left + x as f32,
+                top + y as f32,
+                ' ',
+                Color::Transparent,
+                bg_color,
+            );
+        }
+    }
+}
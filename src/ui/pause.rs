@@ -0,0 +1,95 @@
+use super::{
+    panel::{draw_box, text_y},
+    status_bar,
+};
+use crate::{
+    events::input_map::{Action, InputMap},
+    game::{
+        mode::Mode,
+        pause::{PauseMenu, PauseOption},
+    },
+    renderer::{
+        color::Color,
+        effect::{EffectId, EffectParams},
+        render::Render,
+    },
+};
+
+const MENU_LEFT: f32 = 4.;
+const MENU_TOP: f32 = 6.;
+const MENU_WIDTH: f32 = 30.;
+
+/// [`draw_pause_screen`] will dim the world and draw the options of a [`PauseMenu`]
+/// on top of it using a [`Render`] implementation. Any unsaved progress is pointed
+/// out, and the status bar shows [`Some`] `notification` about the last choice.
+/// Keys are named as bound in the [`InputMap`].
+pub fn draw_pause_screen<R: Render>(
+    menu: &PauseMenu,
+    notification: Option<&str>,
+    input_map: &InputMap,
+    renderer: &mut R,
+) {
+    let tile_size = renderer.get_tile_size();
+    renderer.draw_background_effect(EffectId::Dim, EffectParams::default());
+    draw_box(
+        MENU_LEFT - 1.,
+        MENU_TOP - 3.,
+        MENU_WIDTH + 2.,
+        PauseOption::ALL.len() as f32 + 6.,
+        Color::Black,
+        renderer,
+    );
+    renderer.draw_text(
+        MENU_LEFT * tile_size,
+        text_y(MENU_TOP - 2., tile_size),
+        "PAUSED",
+        Color::Yellow,
+        Color::Black,
+    );
+
+    for (index, option) in PauseOption::ALL.iter().enumerate() {
+        let row = MENU_TOP + index as f32;
+        let is_selected = index == menu.selected;
+        let bg_color = if is_selected {
+            Color::Blue
+        } else {
+            Color::Black
+        };
+        draw_box(MENU_LEFT, row, MENU_WIDTH, 1., bg_color, renderer);
+        renderer.draw_text(
+            MENU_LEFT * tile_size,
+            text_y(row, tile_size),
+            &format!("{} {option}", if is_selected { ">" } else { " " }),
+            Color::White,
+            bg_color,
+        );
+    }
+    if menu.has_unsaved_progress {
+        renderer.draw_text(
+            MENU_LEFT * tile_size,
+            text_y(MENU_TOP + PauseOption::ALL.len() as f32 + 1., tile_size),
+            "You have unsaved progress",
+            Color::Red,
+            Color::Black,
+        );
+    }
+
+    let status = if menu.is_warning {
+        "Unsaved progress will be lost, choose again to quit".to_owned()
+    } else {
+        format!(
+            "Press {} to choose, {} to resume",
+            input_map.describe(Action::Interact),
+            input_map.describe(Action::Pause)
+        )
+    };
+    status_bar::draw_status_bar(Mode::Paused, notification.unwrap_or(&status), renderer);
+}
+
+/// [`get_pause_option_at`] returns [`Some`] index of a [`PauseOption`] at a tile
+/// (`x`, `y`) coordinate, and [`None`] otherwise.
+pub fn get_pause_option_at(x: f32, y: f32) -> Option<usize> {
+    let index = (y - MENU_TOP).floor();
+    let within_list = (MENU_LEFT..MENU_LEFT + MENU_WIDTH).contains(&x) && index >= 0.;
+    (within_list && (index as usize) < PauseOption::ALL.len()).then_some(index as usize)
+}
@@ -1,15 +1,141 @@
-use super::status_bar;
+use super::{panel, status_bar};
 use crate::{
-    game::{mode::Mode, player::Player},
-    maps::map::Map,
-    renderer::render::Render,
+    events::input_map::{Action, InputMap},
+    game::{
+        enemy::Enemy,
+        mode::Mode,
+        npc::Npc,
+        player::{Interactable, Player},
+        stats::XpCurve,
+    },
+    items::world_item::{WORLD_ITEM_SIZE, WorldItem},
+    maps::{building::Building, camera::Camera, map::Map},
+    renderer::{color::Color, render::Render},
 };
 
-/// [`draw_playing_screen`] will draw the main gameplay screen containing a [`Map`] and
-/// [`Player`] using a [`Render`] implementation.
-pub fn draw_playing_screen<R: Render>(map: &Map, player: &Player, renderer: &mut R) {
-    map.draw_tiles(renderer);
-    player.draw_player(renderer);
-    map.draw_buildings(renderer);
-    status_bar::draw_status_bar(Mode::Playing, "Press ESC to quit", renderer);
+// anything drawn in the world that can be in front of or behind something else
+enum Drawable<'a> {
+    Player(&'a Player),
+    Npc(&'a Npc),
+    Enemy(&'a Enemy),
+    WorldItem(&'a WorldItem),
+    Building(&'a Building),
+}
+
+/// [`draw_world`] will draw the part of a [`Map`] seen by a [`Camera`], with the
+/// [`Player`], every [`Npc`], [`Enemy`], [`WorldItem`] and [`Building`] on it, using
+/// a [`Render`] implementation. Whatever stands lower on the screen is drawn in front.
+pub fn draw_world<R: Render>(
+    map: &Map,
+    player: &Player,
+    npcs: &[Npc],
+    enemies: &[Enemy],
+    world_items: &[WorldItem],
+    camera: &Camera,
+    renderer: &mut R,
+) {
+    map.draw_tiles(camera, renderer);
+
+    let mut drawables: Vec<(f32, Drawable)> = vec![(player.get_bottom(), Drawable::Player(player))];
+    drawables.extend(npcs.iter().map(|n| (n.get_bottom(), Drawable::Npc(n))));
+    drawables.extend(enemies.iter().map(|e| (e.get_bottom(), Drawable::Enemy(e))));
+    drawables.extend(
+        world_items
+            .iter()
+            .map(|i| (i.y + WORLD_ITEM_SIZE, Drawable::WorldItem(i))),
+    );
+    drawables.extend(
+        map.buildings
+            .iter()
+            .filter(|b| camera.is_visible(b.x, b.y, b.width, b.height))
+            .map(|b| (b.y + b.height, Drawable::Building(b))),
+    );
+    drawables.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    for (_, drawable) in drawables {
+        match drawable {
+            Drawable::Player(player) => player.draw_player(camera, renderer),
+            Drawable::Npc(npc) => npc.draw_npc(camera, renderer),
+            Drawable::Enemy(enemy) => enemy.draw_enemy(camera, renderer),
+            Drawable::WorldItem(world_item) => world_item.draw_world_item(camera, renderer),
+            Drawable::Building(building) => building.draw_building(camera, renderer),
+        }
+    }
+}
+
+/// [`draw_playing_screen`] will draw the main gameplay screen on top of the world
+/// using a [`Render`] implementation. A prompt is shown over [`Some`]
+/// [`Interactable`] the [`Player`] can use, and the status bar shows the [`Player`]
+/// stats and [`Some`] `notification`. Keys are named as bound in the [`InputMap`].
+pub fn draw_playing_screen<R: Render>(
+    player: &Player,
+    camera: &Camera,
+    interactable: Option<Interactable>,
+    xp_curve: &XpCurve,
+    notification: Option<&str>,
+    input_map: &InputMap,
+    renderer: &mut R,
+) {
+    if let Some(interactable) = interactable {
+        draw_interact_prompt(interactable, camera, input_map, renderer);
+    }
+    let pause_hint = format!("{}: Pause", input_map.describe(Action::Pause));
+    status_bar::draw_status_bar(
+        Mode::Playing,
+        &format!(
+            "{} | {} | {}",
+            get_player_status(player, xp_curve),
+            player.wallet,
+            notification.unwrap_or(&pause_hint)
+        ),
+        renderer,
+    );
+}
+
+// draw what pressing interact will do in the row above what it is used on
+fn draw_interact_prompt<R: Render>(
+    interactable: Interactable,
+    camera: &Camera,
+    input_map: &InputMap,
+    renderer: &mut R,
+) {
+    let tile_size = renderer.get_tile_size();
+    let key = input_map.describe(Action::Interact);
+    let (prompt, x, y) = match interactable {
+        Interactable::Building(building) => (
+            format!("{key}: Use {}", building.building_type),
+            building.x,
+            building.y,
+        ),
+        Interactable::Npc(npc) => (format!("{key}: Talk to {}", npc.name), npc.x, npc.y),
+    };
+    let (x, y) = camera.world_to_screen(x, y - 1.);
+    renderer.draw_text(
+        x * tile_size,
+        panel::text_y(y, tile_size),
+        &prompt,
+        Color::Yellow,
+        Color::Black,
+    );
+}
+
+// the level, health, stamina and experience of a player
+fn get_player_status(player: &Player, xp_curve: &XpCurve) -> String {
+    let stats = &player.stats;
+    let xp = if stats.level >= xp_curve.max_level {
+        "MAX".to_owned()
+    } else {
+        format!(
+            "{}/{}",
+            stats.xp,
+            xp_curve.get_xp_to_next_level(stats.level)
+        )
+    };
+    format!(
+        "Lv {} | HP {}/{} | SP {}/{} | XP {xp}",
+        stats.level,
+        stats.health,
+        stats.get_max_health(),
+        stats.stamina,
+        stats.get_max_stamina()
+    )
 }
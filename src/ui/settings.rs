@@ -0,0 +1,173 @@
+use super::{
+    panel::{draw_box, text_y},
+    status_bar,
+};
+use crate::{
+    events::input_map::{Action, InputMap},
+    game::{
+        mode::Mode,
+        settings::{Settings, SettingsMenu, SettingsOption},
+    },
+    renderer::{color::Color, render::Render},
+};
+
+const MENU_LEFT: f32 = 4.;
+const MENU_TOP: f32 = 4.;
+const MENU_WIDTH: f32 = 44.;
+// how many characters wide the option names are, so their values line up
+const LABEL_WIDTH: usize = 14;
+// how many characters wide the keys of an action are, so the gamepad buttons line up
+const KEYS_WIDTH: usize = 14;
+
+/// [`draw_settings_screen`] will draw the options of a [`SettingsMenu`] with their
+/// draft values, and the key and gamepad bindings of each [`Action`], using a
+/// [`Render`] implementation. Any change that differs from the `launch_settings` and
+/// only applies after a restart is flagged, and the status bar shows [`Some`]
+/// `notification` about the last choice. Options without an effect yet are greyed
+/// out.
+pub fn draw_settings_screen<R: Render>(
+    menu: &SettingsMenu,
+    launch_settings: &Settings,
+    notification: Option<&str>,
+    renderer: &mut R,
+) {
+    let tile_size = renderer.get_tile_size();
+    let last_row = get_option_row(SettingsOption::ALL.len() - 1);
+    draw_box(
+        MENU_LEFT - 1.,
+        MENU_TOP - 3.,
+        MENU_WIDTH + 2.,
+        last_row - MENU_TOP + 5.,
+        Color::Black,
+        renderer,
+    );
+    renderer.draw_text(
+        MENU_LEFT * tile_size,
+        text_y(MENU_TOP - 2., tile_size),
+        "SETTINGS",
+        Color::Yellow,
+        Color::Black,
+    );
+    renderer.draw_text(
+        MENU_LEFT * tile_size,
+        text_y(get_bindings_top(), tile_size),
+        &format!(
+            "{:<width$}{:<KEYS_WIDTH$}Gamepad",
+            "Bindings",
+            "Keys",
+            width = LABEL_WIDTH + 2
+        ),
+        Color::Yellow,
+        Color::Black,
+    );
+
+    for (index, option) in SettingsOption::ALL.iter().enumerate() {
+        let row = get_option_row(index);
+        let is_selected = index == menu.selected;
+        let bg_color = if is_selected {
+            Color::Blue
+        } else {
+            Color::Black
+        };
+        let is_restart_required = menu.is_restart_required(*option, launch_settings);
+        let fg_color = if is_restart_required {
+            Color::Yellow
+        } else if !option.has_effect() {
+            Color::DarkGrey
+        } else if matches!(option, SettingsOption::Binding(_)) {
+            Color::Cyan
+        } else {
+            Color::White
+        };
+        let cursor = if is_selected { ">" } else { " " };
+        let name = option.to_string();
+        let mut label = match option {
+            SettingsOption::Binding(action) => {
+                let keys = if menu.rebinding == Some(*action) {
+                    "<press a key>".to_owned()
+                } else {
+                    menu.draft.get_value(*option).unwrap_or_default()
+                };
+                let buttons: Vec<String> = menu
+                    .draft
+                    .input_map
+                    .get_buttons(*action)
+                    .iter()
+                    .map(|button| button.to_string())
+                    .collect();
+                format!(
+                    "{cursor} {name:<LABEL_WIDTH$}{keys:<KEYS_WIDTH$}{}",
+                    buttons.join(", ")
+                )
+            }
+            _ => match menu.draft.get_value(*option) {
+                Some(value) => format!("{cursor} {name:<LABEL_WIDTH$}< {value} >"),
+                None => format!("{cursor} {name}"),
+            },
+        };
+        if is_restart_required {
+            label += " (restart required)";
+        }
+        if !option.has_effect() {
+            label += " (no effect yet)";
+        }
+        draw_box(MENU_LEFT, row, MENU_WIDTH, 1., bg_color, renderer);
+        renderer.draw_text(
+            MENU_LEFT * tile_size,
+            text_y(row, tile_size),
+            &label,
+            fg_color,
+            bg_color,
+        );
+    }
+
+    let status = match menu.rebinding {
+        Some(action) => format!(
+            "Press a key for {action}, or {} to cancel",
+            menu.draft.input_map.describe(Action::Pause)
+        ),
+        None => notification
+            .map(str::to_owned)
+            .unwrap_or_else(|| get_default_status(&launch_settings.input_map)),
+    };
+    status_bar::draw_status_bar(Mode::Settings, &status, renderer);
+}
+
+// the keys are named as bound at launch, since that is the input map in use until a restart
+fn get_default_status(input_map: &InputMap) -> String {
+    format!(
+        "{}/{} to change, {} to choose, {} to go back",
+        input_map.describe(Action::Left),
+        input_map.describe(Action::Right),
+        input_map.describe(Action::Interact),
+        input_map.describe(Action::Pause)
+    )
+}
+
+/// [`get_settings_option_at`] returns [`Some`] index of a [`SettingsOption`] at a
+/// tile (`x`, `y`) coordinate, and [`None`] otherwise.
+pub fn get_settings_option_at(x: f32, y: f32) -> Option<usize> {
+    let within_columns = (MENU_LEFT..MENU_LEFT + MENU_WIDTH).contains(&x);
+    (0..SettingsOption::ALL.len())
+        .find(|index| within_columns && get_option_row(*index) == y.floor())
+}
+
+// the bindings are listed under a heading below the other options, and save and
+// back a row below them
+fn get_option_row(index: usize) -> f32 {
+    let gap = match SettingsOption::ALL[index] {
+        SettingsOption::Binding(_) => 2.,
+        SettingsOption::Save | SettingsOption::Back => 3.,
+        _ => 0.,
+    };
+    MENU_TOP + index as f32 + gap
+}
+
+// the heading of the bindings is the row above the first of them
+fn get_bindings_top() -> f32 {
+    let first_binding = SettingsOption::ALL
+        .iter()
+        .position(|option| matches!(option, SettingsOption::Binding(_)))
+        .unwrap_or_default();
+    get_option_row(first_binding) - 1.
+}